@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
 pub enum SQLStatement {
     Select(SelectStatement),
     Insert(InsertStatement),
@@ -6,107 +8,271 @@ pub enum SQLStatement {
     Delete(DeleteStatement),
     Create(CreateStatement),
     Drop(DropStatement),
+    Explain(Box<SelectStatement>),
+    Savepoint(SavepointStatement),
+    Transaction(TransactionStatement),
+    Alter(AlterStatement),
+    Comment(CommentStatement),
+    Vacuum(VacuumStatement),
+}
+
+// COMMENT ON ... IS NULL removes the comment and is parsed with `comment` set to None.
+#[derive(Debug, PartialEq)]
+pub struct CommentStatement {
+    pub target: CommentTarget,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CommentTarget {
+    Table(String),
+    Column { table: String, column: String },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AlterStatement {
+    pub table: String,
+    pub action: AlterAction,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+pub enum AlterAction {
+    RenameTable(String),
+    RenameColumn { from: String, to: String },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TransactionStatement {
+    Begin,
+    Commit,
+    Rollback,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SavepointStatement {
+    Define(String),
+    RollbackTo(String),
+    Release(String),
+}
+
+#[derive(Debug, PartialEq)]
 pub struct InsertStatement {
     pub table: String,
     pub columns: Vec<String>,
-    pub values: Vec<Expression>,
+    pub values: Vec<Vec<Expression>>,
+    pub returning: Option<Vec<SelectColumn>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct UpdateStatement {
     pub table: String,
     pub assignments: Vec<Assignment>,
     pub where_clause: Option<WhereClause>,
+    pub returning: Option<Vec<SelectColumn>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Assignment {
     pub column: String,
     pub value: Expression,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct DeleteStatement {
     pub table: String,
     pub where_clause: Option<WhereClause>,
+    pub returning: Option<Vec<SelectColumn>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct CreateStatement {
     pub table: String,
     pub columns: Vec<ColumnDefinition>,
+    pub table_constraints: Vec<TableConstraint>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct ColumnDefinition {
     pub name: String,
     pub data_type: DataType,
     pub constraints: Vec<ColumnConstraint>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum DataType {
     Integer,
     Float,
     Varchar(Option<usize>),
+    Char(Option<usize>),
+    Text,
     Boolean,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum ColumnConstraint {
     PrimaryKey,
     NotNull,
     Unique,
+    References(ForeignKeyReference),
+    // Omitted or NULL values are filled from a per-column counter.
+    AutoIncrement,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TableConstraint {
+    PrimaryKey(Vec<String>),
+    Unique(Vec<String>),
+    ForeignKey {
+        columns: Vec<String>,
+        references: ForeignKeyReference,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ForeignKeyReference {
+    pub table: String,
+    pub columns: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReferentialAction {
+    Cascade,
+    SetNull,
+    Restrict,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct DropStatement {
     pub table: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+pub struct VacuumStatement {
+    pub table: String,
+}
+
+#[derive(Debug, PartialEq)]
 pub struct SelectStatement {
+    // SELECT DISTINCT drops duplicate result rows.
+    pub distinct: bool,
     pub columns: Vec<SelectColumn>,
     pub from: Option<String>,
     pub where_clause: Option<WhereClause>,
+    pub order_by: Vec<OrderByItem>,
+    pub limit: Option<u64>,
+    // SELECT ... FOR UPDATE locks the rows it returns.
+    pub for_update: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum SelectColumn {
     All,
     Column(String),
+    Expression(Expression),
+    Aggregate(AggregateCall),
+}
+
+// `argument` is None for COUNT(*).
+#[derive(Debug, PartialEq)]
+pub struct AggregateCall {
+    pub function: AggregateFunction,
+    pub argument: Option<Expression>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AggregateFunction {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OrderByItem {
+    pub key: OrderKey,
+    pub descending: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OrderKey {
+    Expression(Expression),
+    Position(usize),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct WhereClause {
     pub condition: Condition,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Condition {
     Comparison(ComparisonCondition),
     Logical(LogicalCondition),
     Not(Box<Condition>),
     NullCheck(NullCheckCondition),
+    In(InCondition),
+    Like(LikeCondition),
+    Between(BetweenCondition),
+    TupleIn(TupleInCondition),
+}
+
+// (a, b) IN ((1, 2), (3, 4)) matches when every column equals the value in
+// the same position of one of the rows. Values are literals or `?` parameters.
+#[derive(Debug, PartialEq)]
+pub struct TupleInCondition {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Expression>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct ComparisonCondition {
     pub operator: ComparisonOperator,
     pub left: Expression,
     pub right: Expression,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+pub struct InCondition {
+    pub identifier: String,
+    pub values: InValues,
+}
+
+// a BETWEEN low AND high includes both bounds.
+#[derive(Debug, PartialEq)]
+pub struct BetweenCondition {
+    pub identifier: String,
+    pub low: Expression,
+    pub high: Expression,
+    pub negated: bool,
+}
+
+// `%` matches any run of characters and `_` a single one; a character preceded
+// by `escape` is matched literally.
+#[derive(Debug, PartialEq)]
+pub struct LikeCondition {
+    pub identifier: String,
+    pub pattern: String,
+    pub escape: Option<char>,
+    pub negated: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InValues {
+    // Literals or `?` parameters.
+    List(Vec<Expression>),
+    Subquery(Box<SelectStatement>),
+}
+
+#[derive(Debug, PartialEq)]
 pub enum NullCheckCondition {
     IsNull { identifier: String },
     IsNotNull { identifier: String },
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum ComparisonOperator {
     Equal,
     NotEqual,
@@ -114,30 +280,603 @@ pub enum ComparisonOperator {
     LessThan,
     GreaterThanOrEqual,
     LessThanOrEqual,
+    // NULL-safe comparisons: two NULLs are not distinct from each other.
+    IsDistinctFrom,
+    IsNotDistinctFrom,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct LogicalCondition {
     pub left: Box<Condition>,
     pub operator: LogicalOperator,
     pub right: Box<Condition>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum LogicalOperator {
     And,
     Or,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Expression {
     Identifier(String),
     Literal(Literal),
+    Arithmetic(ArithmeticExpression),
+    Concat(ConcatExpression),
+    Function(FunctionCall),
+    Parameter(usize),
+    // Unary minus; a negated numeric literal is folded into the literal instead.
+    Negate(Box<Expression>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ConcatExpression {
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FunctionCall {
+    pub function: ScalarFunction,
+    pub arguments: Vec<Expression>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ScalarFunction {
+    Upper,
+    Lower,
+    Length,
+    Substr,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ArithmeticExpression {
+    pub left: Box<Expression>,
+    pub operator: ArithmeticOperator,
+    pub right: Box<Expression>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+pub enum ArithmeticOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, PartialEq)]
 pub enum Literal {
     String(String),
+    // A number written without a decimal point, kept exact.
+    Integer(i64),
     Number(f64),
     Boolean(bool),
 }
+
+impl Literal {
+    // Integers and decimals are both numbers when checking that values agree.
+    pub fn same_type(&self, other: &Literal) -> bool {
+        let numeric =
+            |literal: &Literal| matches!(literal, Literal::Integer(_) | Literal::Number(_));
+        (numeric(self) && numeric(other))
+            || std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+// Display renders the AST back into canonical SQL: keywords uppercased, one
+// clause per line and only the parentheses needed to preserve the tree.
+impl fmt::Display for SQLStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SQLStatement::Select(select) => write!(f, "{}", select),
+            SQLStatement::Insert(insert) => write!(f, "{}", insert),
+            SQLStatement::Update(update) => write!(f, "{}", update),
+            SQLStatement::Delete(delete) => write!(f, "{}", delete),
+            SQLStatement::Create(create) => write!(f, "{}", create),
+            SQLStatement::Drop(drop) => write!(f, "{}", drop),
+            SQLStatement::Explain(select) => write!(f, "EXPLAIN {}", select),
+            SQLStatement::Savepoint(savepoint) => write!(f, "{}", savepoint),
+            SQLStatement::Vacuum(vacuum) => write!(f, "VACUUM {}", vacuum.table),
+            SQLStatement::Transaction(transaction) => match transaction {
+                TransactionStatement::Begin => write!(f, "BEGIN"),
+                TransactionStatement::Commit => write!(f, "COMMIT"),
+                TransactionStatement::Rollback => write!(f, "ROLLBACK"),
+            },
+            SQLStatement::Comment(comment) => {
+                match &comment.target {
+                    CommentTarget::Table(table) => write!(f, "COMMENT ON TABLE {}", table)?,
+                    CommentTarget::Column { table, column } => {
+                        write!(f, "COMMENT ON COLUMN {}.{}", table, column)?
+                    }
+                }
+                match &comment.comment {
+                    Some(text) => write!(f, " IS {}", Literal::String(text.clone())),
+                    None => write!(f, " IS NULL"),
+                }
+            }
+            SQLStatement::Alter(alter) => {
+                write!(f, "ALTER TABLE {} ", alter.table)?;
+                match &alter.action {
+                    AlterAction::RenameTable(name) => write!(f, "RENAME TO {}", name),
+                    AlterAction::RenameColumn { from, to } => {
+                        write!(f, "RENAME COLUMN {} TO {}", from, to)
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for SavepointStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SavepointStatement::Define(name) => write!(f, "SAVEPOINT {}", name),
+            SavepointStatement::RollbackTo(name) => write!(f, "ROLLBACK TO SAVEPOINT {}", name),
+            SavepointStatement::Release(name) => write!(f, "RELEASE SAVEPOINT {}", name),
+        }
+    }
+}
+
+impl fmt::Display for SelectStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_clauses(f, "\n")
+    }
+}
+
+impl SelectStatement {
+    fn write_clauses(&self, f: &mut fmt::Formatter<'_>, separator: &str) -> fmt::Result {
+        write!(f, "SELECT ")?;
+        if self.distinct {
+            write!(f, "DISTINCT ")?;
+        }
+        write!(f, "{}", join(&self.columns, ", "))?;
+        if let Some(from) = &self.from {
+            write!(f, "{}FROM {}", separator, from)?;
+        }
+        if let Some(where_clause) = &self.where_clause {
+            write!(f, "{}{}", separator, where_clause)?;
+        }
+        if !self.order_by.is_empty() {
+            write!(f, "{}ORDER BY {}", separator, join(&self.order_by, ", "))?;
+        }
+        if let Some(limit) = self.limit {
+            write!(f, "{}LIMIT {}", separator, limit)?;
+        }
+        if self.for_update {
+            write!(f, "{}FOR UPDATE", separator)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SelectColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectColumn::All => write!(f, "*"),
+            SelectColumn::Column(name) => write!(f, "{}", name),
+            SelectColumn::Expression(expression) => write!(f, "{}", expression),
+            SelectColumn::Aggregate(aggregate) => match &aggregate.argument {
+                Some(argument) => write!(f, "{}({})", aggregate.function, argument),
+                None => write!(f, "{}(*)", aggregate.function),
+            },
+        }
+    }
+}
+
+impl fmt::Display for AggregateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AggregateFunction::Count => "COUNT",
+            AggregateFunction::Sum => "SUM",
+            AggregateFunction::Avg => "AVG",
+            AggregateFunction::Min => "MIN",
+            AggregateFunction::Max => "MAX",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for OrderByItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            OrderKey::Expression(expression) => write!(f, "{}", expression)?,
+            OrderKey::Position(position) => write!(f, "{}", position)?,
+        }
+        if self.descending {
+            write!(f, " DESC")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for InsertStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "INSERT INTO {}", self.table)?;
+        if !self.columns.is_empty() {
+            write!(f, " ({})", self.columns.join(", "))?;
+        }
+        let rows: Vec<String> = self
+            .values
+            .iter()
+            .map(|row| format!("({})", join(row, ", ")))
+            .collect();
+        write!(f, "\nVALUES {}", rows.join(", "))?;
+        write_returning(f, &self.returning)
+    }
+}
+
+impl fmt::Display for UpdateStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "UPDATE {}\nSET {}",
+            self.table,
+            join(&self.assignments, ", ")
+        )?;
+        if let Some(where_clause) = &self.where_clause {
+            write!(f, "\n{}", where_clause)?;
+        }
+        write_returning(f, &self.returning)
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.column, self.value)
+    }
+}
+
+impl fmt::Display for DeleteStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DELETE FROM {}", self.table)?;
+        if let Some(where_clause) = &self.where_clause {
+            write!(f, "\n{}", where_clause)?;
+        }
+        write_returning(f, &self.returning)
+    }
+}
+
+fn write_returning(
+    f: &mut fmt::Formatter<'_>,
+    returning: &Option<Vec<SelectColumn>>,
+) -> fmt::Result {
+    match returning {
+        Some(columns) => write!(f, "\nRETURNING {}", join(columns, ", ")),
+        None => Ok(()),
+    }
+}
+
+impl fmt::Display for CreateStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut elements: Vec<String> = self.columns.iter().map(|c| c.to_string()).collect();
+        elements.extend(self.table_constraints.iter().map(|c| c.to_string()));
+        write!(
+            f,
+            "CREATE TABLE {} (\n    {}\n)",
+            self.table,
+            elements.join(",\n    ")
+        )
+    }
+}
+
+impl fmt::Display for ColumnDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.data_type)?;
+        for constraint in &self.constraints {
+            write!(f, " {}", constraint)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataType::Integer => write!(f, "INTEGER"),
+            DataType::Float => write!(f, "FLOAT"),
+            DataType::Varchar(None) => write!(f, "VARCHAR"),
+            DataType::Varchar(Some(size)) => write!(f, "VARCHAR({})", size),
+            DataType::Char(None) => write!(f, "CHAR"),
+            DataType::Char(Some(size)) => write!(f, "CHAR({})", size),
+            DataType::Text => write!(f, "TEXT"),
+            DataType::Boolean => write!(f, "BOOLEAN"),
+        }
+    }
+}
+
+impl fmt::Display for ColumnConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnConstraint::PrimaryKey => write!(f, "PRIMARY KEY"),
+            ColumnConstraint::NotNull => write!(f, "NOT NULL"),
+            ColumnConstraint::Unique => write!(f, "UNIQUE"),
+            ColumnConstraint::References(references) => write!(f, "REFERENCES {}", references),
+            ColumnConstraint::AutoIncrement => write!(f, "AUTOINCREMENT"),
+        }
+    }
+}
+
+impl fmt::Display for TableConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableConstraint::PrimaryKey(columns) => {
+                write!(f, "PRIMARY KEY ({})", columns.join(", "))
+            }
+            TableConstraint::Unique(columns) => write!(f, "UNIQUE ({})", columns.join(", ")),
+            TableConstraint::ForeignKey {
+                columns,
+                references,
+            } => write!(
+                f,
+                "FOREIGN KEY ({}) REFERENCES {}",
+                columns.join(", "),
+                references
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ForeignKeyReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.table, self.columns.join(", "))?;
+        if let Some(action) = &self.on_delete {
+            write!(f, " ON DELETE {}", action)?;
+        }
+        if let Some(action) = &self.on_update {
+            write!(f, " ON UPDATE {}", action)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ReferentialAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReferentialAction::Cascade => write!(f, "CASCADE"),
+            ReferentialAction::SetNull => write!(f, "SET NULL"),
+            ReferentialAction::Restrict => write!(f, "RESTRICT"),
+        }
+    }
+}
+
+impl fmt::Display for DropStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DROP TABLE {}", self.table)
+    }
+}
+
+impl fmt::Display for WhereClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WHERE {}", self.condition)
+    }
+}
+
+impl Condition {
+    // Binding strength used to decide where parentheses are required.
+    fn precedence(&self) -> u8 {
+        match self {
+            Condition::Logical(LogicalCondition {
+                operator: LogicalOperator::Or,
+                ..
+            }) => 1,
+            Condition::Logical(LogicalCondition {
+                operator: LogicalOperator::And,
+                ..
+            }) => 2,
+            _ => 3,
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Comparison(comparison) => write!(
+                f,
+                "{} {} {}",
+                comparison.left, comparison.operator, comparison.right
+            ),
+            Condition::Logical(logical) => {
+                let precedence = self.precedence();
+                // Logical operators associate to the left, so an equal-precedence
+                // right operand keeps its parentheses.
+                write_operand(f, &logical.left, logical.left.precedence() < precedence)?;
+                write!(f, " {} ", logical.operator)?;
+                write_operand(f, &logical.right, logical.right.precedence() <= precedence)
+            }
+            Condition::Not(condition) => write!(f, "NOT ({})", condition),
+            Condition::NullCheck(NullCheckCondition::IsNull { identifier }) => {
+                write!(f, "{} IS NULL", identifier)
+            }
+            Condition::NullCheck(NullCheckCondition::IsNotNull { identifier }) => {
+                write!(f, "{} IS NOT NULL", identifier)
+            }
+            Condition::In(in_condition) => {
+                write!(f, "{} IN (", in_condition.identifier)?;
+                match &in_condition.values {
+                    InValues::List(values) => write!(f, "{}", join(values, ", "))?,
+                    InValues::Subquery(select) => select.write_clauses(f, " ")?,
+                }
+                write!(f, ")")
+            }
+            Condition::TupleIn(tuple_in) => {
+                let rows: Vec<String> = tuple_in
+                    .rows
+                    .iter()
+                    .map(|row| format!("({})", join(row, ", ")))
+                    .collect();
+                write!(
+                    f,
+                    "({}) IN ({})",
+                    tuple_in.columns.join(", "),
+                    rows.join(", ")
+                )
+            }
+            Condition::Like(like) => {
+                write!(f, "{} ", like.identifier)?;
+                if like.negated {
+                    write!(f, "NOT ")?;
+                }
+                write!(f, "LIKE {}", Literal::String(like.pattern.clone()))?;
+                if let Some(escape) = like.escape {
+                    write!(f, " ESCAPE {}", Literal::String(escape.to_string()))?;
+                }
+                Ok(())
+            }
+            Condition::Between(between) => {
+                write!(f, "{} ", between.identifier)?;
+                if between.negated {
+                    write!(f, "NOT ")?;
+                }
+                write!(f, "BETWEEN {} AND {}", between.low, between.high)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self {
+            ComparisonOperator::Equal => "=",
+            ComparisonOperator::NotEqual => "<>",
+            ComparisonOperator::GreaterThan => ">",
+            ComparisonOperator::LessThan => "<",
+            ComparisonOperator::GreaterThanOrEqual => ">=",
+            ComparisonOperator::LessThanOrEqual => "<=",
+            ComparisonOperator::IsDistinctFrom => "IS DISTINCT FROM",
+            ComparisonOperator::IsNotDistinctFrom => "IS NOT DISTINCT FROM",
+        };
+        write!(f, "{}", operator)
+    }
+}
+
+impl fmt::Display for LogicalOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogicalOperator::And => write!(f, "AND"),
+            LogicalOperator::Or => write!(f, "OR"),
+        }
+    }
+}
+
+impl Expression {
+    fn precedence(&self) -> u8 {
+        match self {
+            Expression::Concat(_) => 0,
+            Expression::Arithmetic(ArithmeticExpression {
+                operator: ArithmeticOperator::Add | ArithmeticOperator::Subtract,
+                ..
+            }) => 1,
+            Expression::Arithmetic(_) => 2,
+            _ => 3,
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Identifier(identifier) => write!(f, "{}", identifier),
+            Expression::Literal(literal) => write!(f, "{}", literal),
+            Expression::Parameter(_) => write!(f, "?"),
+            Expression::Negate(operand) => {
+                // Parenthesize a leading minus too, since "--" starts a comment.
+                let signed = operand.to_string().starts_with('-');
+                write!(f, "-")?;
+                write_operand(f, operand, signed || operand.precedence() < 3)
+            }
+            Expression::Concat(concat) => {
+                write!(f, "{}", concat.left)?;
+                write!(f, " || ")?;
+                write_operand(f, &concat.right, concat.right.precedence() == 0)
+            }
+            Expression::Function(call) => {
+                write!(f, "{}({})", call.function, join(&call.arguments, ", "))
+            }
+            Expression::Arithmetic(arithmetic) => {
+                let precedence = self.precedence();
+                write_operand(
+                    f,
+                    &arithmetic.left,
+                    arithmetic.left.precedence() < precedence,
+                )?;
+                write!(f, " {} ", arithmetic.operator)?;
+                write_operand(
+                    f,
+                    &arithmetic.right,
+                    arithmetic.right.precedence() <= precedence,
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for ScalarFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ScalarFunction::Upper => "UPPER",
+            ScalarFunction::Lower => "LOWER",
+            ScalarFunction::Length => "LENGTH",
+            ScalarFunction::Substr => "SUBSTR",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for ArithmeticOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self {
+            ArithmeticOperator::Add => "+",
+            ArithmeticOperator::Subtract => "-",
+            ArithmeticOperator::Multiply => "*",
+            ArithmeticOperator::Divide => "/",
+        };
+        write!(f, "{}", operator)
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::String(value) => {
+                write!(f, "'")?;
+                for c in value.chars() {
+                    match c {
+                        '\'' => write!(f, "\\'")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        _ => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "'")
+            }
+            Literal::Integer(integer) => write!(f, "{}", integer),
+            // A decimal keeps its point so it reads back as a decimal, not an integer.
+            Literal::Number(number) if number.fract() == 0.0 => write!(f, "{}.0", number),
+            Literal::Number(number) => write!(f, "{}", number),
+            Literal::Boolean(true) => write!(f, "TRUE"),
+            Literal::Boolean(false) => write!(f, "FALSE"),
+        }
+    }
+}
+
+fn write_operand<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    operand: &T,
+    parenthesize: bool,
+) -> fmt::Result {
+    match parenthesize {
+        true => write!(f, "({})", operand),
+        false => write!(f, "{}", operand),
+    }
+}
+
+fn join<T: fmt::Display>(items: &[T], separator: &str) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
@@ -0,0 +1,28 @@
+use std::{collections::HashSet, rc::Rc};
+
+// Hands out one shared `Rc<str>` per distinct string, so a name that appears
+// many times in a script is allocated once.
+#[derive(Default)]
+pub struct Interner {
+    strings: HashSet<Rc<str>>,
+}
+
+impl Interner {
+    pub fn intern(&mut self, value: &str) -> Rc<str> {
+        if let Some(interned) = self.strings.get(value) {
+            return Rc::clone(interned);
+        }
+        let interned: Rc<str> = Rc::from(value);
+        self.strings.insert(Rc::clone(&interned));
+        interned
+    }
+
+    // Number of distinct strings interned so far.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
@@ -1,34 +1,186 @@
 #![allow(dead_code, clippy::needless_return)]
 use self::{
     ast::{
-        Assignment, ColumnConstraint, ColumnDefinition, ComparisonCondition, ComparisonOperator,
-        Condition, CreateStatement, DataType, DropStatement, Expression, InsertStatement, Literal,
-        LogicalCondition, LogicalOperator, NullCheckCondition, SQLStatement, SelectColumn,
-        SelectStatement, WhereClause,
+        AggregateCall, AggregateFunction, AlterAction, AlterStatement, ArithmeticExpression,
+        ArithmeticOperator, Assignment, BetweenCondition, ColumnConstraint, ColumnDefinition,
+        CommentStatement, CommentTarget, ComparisonCondition, ComparisonOperator, ConcatExpression,
+        Condition, CreateStatement, DataType, DropStatement, Expression, ForeignKeyReference,
+        FunctionCall, InCondition, InValues, InsertStatement, LikeCondition, Literal,
+        LogicalCondition, LogicalOperator, NullCheckCondition, OrderByItem, OrderKey,
+        ReferentialAction, SQLStatement, SavepointStatement, ScalarFunction, SelectColumn,
+        SelectStatement, TableConstraint, TransactionStatement, TupleInCondition, VacuumStatement,
+        WhereClause,
     },
-    scanner::Scanner,
+    scanner::{Scanner, ScannerConfig},
     sql_token_types::SQLTokenTypes,
     token::Token,
 };
 
 pub mod ast;
+pub mod interner;
 pub mod scanner;
 pub mod sql_token_types;
 pub mod token;
 
+// Scans a query into tokens, ending with Eof, ready for Parser::from_tokens.
+pub fn tokenize(sql: &str) -> Result<Vec<Token>, String> {
+    Scanner::new(sql.to_string()).scan_tokens()
+}
+
+// Renders the token stream of a query one token per line as TYPE(lexeme)@line:column,
+// which is handy when diagnosing scanner bugs.
+pub fn tokenize_to_string(sql: &str) -> Result<String, String> {
+    let tokens = tokenize(sql)?;
+    Ok(tokens
+        .iter()
+        .map(|token| token.to_string())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+// Parses a statement and re-emits it in canonical form: keywords uppercased,
+// one clause per line and consistent spacing. Formatting is idempotent.
+pub fn format_sql(sql: &str) -> Result<String, String> {
+    let statement = Parser::new(sql.to_string())?.parse()?;
+    Ok(statement.to_string())
+}
+
+// How deeply parentheses, NOT, function calls and subqueries may nest before
+// parsing is abandoned instead of risking a stack overflow.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+// A statement that failed to parse, located by its first token.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub line: i64,
+    pub column: i64,
+}
+
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    parameter_count: usize,
+    depth: usize,
+    max_depth: usize,
 }
 
 impl Parser {
-    pub fn new(source: String) -> Self {
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens();
-        Self { tokens, current: 0 }
+    pub fn new(source: String) -> Result<Self, String> {
+        Self::new_with_config(source, ScannerConfig::default())
+    }
+
+    pub fn new_with_config(source: String, config: ScannerConfig) -> Result<Self, String> {
+        let mut scanner = Scanner::new_with_config(source, false, config);
+        Ok(Self::from_tokens(scanner.scan_tokens()?))
+    }
+
+    // Builds a parser over an already scanned token stream. An Eof token is
+    // appended if the stream does not end with one.
+    pub fn from_tokens(mut tokens: Vec<Token>) -> Self {
+        // Comment trivia carries no meaning for the grammar.
+        tokens.retain(|token| token.token_type != SQLTokenTypes::Comment);
+        if tokens.last().map(|token| &token.token_type) != Some(&SQLTokenTypes::Eof) {
+            let (line, column) = match tokens.last() {
+                Some(token) => (token.line, token.column + token.lexeme.len() as i64),
+                None => (1, 1),
+            };
+            tokens.push(Token {
+                token_type: SQLTokenTypes::Eof,
+                lexeme: "".into(),
+                literal: None,
+                line,
+                column,
+            });
+        }
+        Self {
+            tokens,
+            current: 0,
+            parameter_count: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    // Number of `?` placeholders seen so far, used to check bound values against.
+    pub fn parameter_count(&self) -> usize {
+        self.parameter_count
+    }
+
+    // Parses a standalone expression such as `price * 2 + 1` without a surrounding statement.
+    pub fn parse_expression_str(sql: &str) -> Result<Expression, String> {
+        let mut parser = Self::new(sql.to_string())?;
+        let expression = parser.expression()?;
+        if !parser.is_at_end() {
+            return Err(format!(
+                "Unexpected token '{}' after end of expression",
+                parser.peek().lexeme
+            ));
+        }
+
+        Ok(expression)
     }
 
     pub fn parse(&mut self) -> Result<SQLStatement, String> {
+        let statement = self.statement()?;
+
+        self.match_token(SQLTokenTypes::Semicolon);
+        if !self.is_at_end() {
+            return Err(format!(
+                "Unexpected token '{}' after end of statement",
+                self.peek().lexeme
+            ));
+        }
+
+        Ok(statement)
+    }
+
+    // Parses a `;` separated script. A statement that fails is skipped up to the
+    // next `;` so every problem in the script is reported, not just the first.
+    pub fn parse_with_recovery(&mut self) -> (Vec<SQLStatement>, Vec<ParseError>) {
+        let mut statements = Vec::new();
+        let mut errors = Vec::new();
+        while !self.is_at_end() {
+            if self.match_token(SQLTokenTypes::Semicolon) {
+                continue;
+            }
+
+            let (line, column) = (self.peek().line, self.peek().column);
+            let result = self.statement().and_then(|statement| {
+                if self.is_at_end() || self.match_token(SQLTokenTypes::Semicolon) {
+                    Ok(statement)
+                } else {
+                    Err(format!(
+                        "Unexpected token '{}' after end of statement",
+                        self.peek().lexeme
+                    ))
+                }
+            });
+
+            match result {
+                Ok(statement) => statements.push(statement),
+                Err(message) => {
+                    errors.push(ParseError {
+                        message,
+                        line,
+                        column,
+                    });
+                    while !self.is_at_end() && !self.match_token(SQLTokenTypes::Semicolon) {
+                        self.advance();
+                    }
+                }
+            }
+        }
+
+        (statements, errors)
+    }
+
+    fn statement(&mut self) -> Result<SQLStatement, String> {
         match self.peek().token_type {
             SQLTokenTypes::Select => self.select_statement(),
             SQLTokenTypes::Insert => self.insert_statement(),
@@ -36,56 +188,214 @@ impl Parser {
             SQLTokenTypes::Delete => self.delete_statement(),
             SQLTokenTypes::Create => self.create_statement(),
             SQLTokenTypes::Drop => self.drop_statement(),
+            SQLTokenTypes::Explain => self.explain_statement(),
+            SQLTokenTypes::Alter => self.alter_statement(),
+            SQLTokenTypes::Vacuum => self.vacuum_statement(),
+            // COMMENT is not reserved since it is a common column name.
+            SQLTokenTypes::Identifier
+                if self.peek().lexeme.eq_ignore_ascii_case("COMMENT")
+                    && self.check_next(SQLTokenTypes::On) =>
+            {
+                self.comment_statement()
+            }
+            SQLTokenTypes::Rollback if self.check_next(SQLTokenTypes::To) => {
+                self.savepoint_statement()
+            }
+            SQLTokenTypes::Savepoint | SQLTokenTypes::Release => self.savepoint_statement(),
+            SQLTokenTypes::Begin | SQLTokenTypes::Commit | SQLTokenTypes::Rollback => {
+                self.transaction_statement()
+            }
             _ => Err("Unexpected statement type".to_string()),
         }
     }
 
     fn select_statement(&mut self) -> Result<SQLStatement, String> {
+        Ok(SQLStatement::Select(self.parse_select()?))
+    }
+
+    fn parse_select(&mut self) -> Result<SelectStatement, String> {
         self.consume(SQLTokenTypes::Select, "expected select keyword")?;
+        let distinct = self.match_token(SQLTokenTypes::Distinct);
+        let columns = self.parse_select_list()?;
+
+        // Without FROM the select list is evaluated once, e.g. SELECT 1 + 1
+        let from = if self.match_token(SQLTokenTypes::From) {
+            if !self.check(SQLTokenTypes::Identifier) {
+                return Err("Expected table name after FROM".to_string());
+            }
+            Some(self.advance().lexeme.to_string())
+        } else if columns.contains(&SelectColumn::All) {
+            return Err("SELECT * requires a FROM clause".to_string());
+        } else {
+            None
+        };
+
+        let where_clause = if self.match_token(SQLTokenTypes::Where) {
+            if from.is_none() {
+                return Err("WHERE requires a FROM clause".to_string());
+            }
+            Some(self.where_clause()?)
+        } else {
+            None
+        };
+
+        let order_by = if self.match_token(SQLTokenTypes::Order) {
+            self.consume(SQLTokenTypes::By, "Expect BY after ORDER")?;
+            self.parse_order_by(&columns)?
+        } else {
+            Vec::new()
+        };
+
+        let limit = if self.match_token(SQLTokenTypes::Limit) {
+            Some(self.parse_limit()?)
+        } else {
+            None
+        };
+
+        let for_update = self.match_token(SQLTokenTypes::For);
+        if for_update {
+            self.consume(SQLTokenTypes::Update, "Expect UPDATE after FOR")?;
+            if from.is_none() {
+                return Err("FOR UPDATE requires a FROM clause".to_string());
+            }
+        }
+
+        Ok(SelectStatement {
+            distinct,
+            columns,
+            from,
+            where_clause,
+            order_by,
+            limit,
+            for_update,
+        })
+    }
+
+    // LIMIT 10
+    fn parse_limit(&mut self) -> Result<u64, String> {
+        let token = self.consume(
+            SQLTokenTypes::Number,
+            "Expected a non-negative integer after LIMIT",
+        )?;
+        token
+            .literal
+            .as_ref()
+            .and_then(|literal| literal.downcast_ref::<i64>())
+            .and_then(|number| u64::try_from(*number).ok())
+            .ok_or_else(|| {
+                format!(
+                    "LIMIT must be a non-negative integer, found {}",
+                    token.lexeme
+                )
+            })
+    }
+
+    // The comma separated output columns of SELECT and RETURNING.
+    fn parse_select_list(&mut self) -> Result<Vec<SelectColumn>, String> {
         let mut columns = Vec::new();
         loop {
-            if self.check(SQLTokenTypes::Star) {
-                self.consume(SQLTokenTypes::Star, "expected *")?;
+            // A `*` where an operand is expected selects all columns; a `*` following an
+            // operand is multiplication and is handled by the expression parser.
+            if self.match_token(SQLTokenTypes::Star) {
                 columns.push(SelectColumn::All);
-                break;
-            } else if self.check(SQLTokenTypes::Identifier) {
-                columns.push(SelectColumn::Column(self.advance().lexeme.clone()));
+            } else if let Some(function) = self.aggregate_function() {
+                columns.push(self.aggregate_call(function)?);
             } else {
-                return Err("Expected column name or *".to_string());
+                columns.push(match self.expression()? {
+                    Expression::Identifier(name) => SelectColumn::Column(name),
+                    expression => SelectColumn::Expression(expression),
+                });
             }
 
             if !self.match_token(SQLTokenTypes::Comma) {
                 break;
             }
         }
+        Ok(columns)
+    }
 
-        self.consume(SQLTokenTypes::From, "Expect FROM after select columns")?;
-        let from = if self.check(SQLTokenTypes::Identifier) {
-            Some(self.advance().lexeme.clone())
-        } else {
-            return Err("Expected table name after FROM".to_string());
-        };
+    // Aggregates are only recognised as whole select-list items, e.g. COUNT(*) or SUM(a).
+    fn aggregate_function(&self) -> Option<AggregateFunction> {
+        if !self.check(SQLTokenTypes::Identifier) || !self.check_next(SQLTokenTypes::Leftparen) {
+            return None;
+        }
+        match self.peek().lexeme.to_uppercase().as_str() {
+            "COUNT" => Some(AggregateFunction::Count),
+            "SUM" => Some(AggregateFunction::Sum),
+            "AVG" => Some(AggregateFunction::Avg),
+            "MIN" => Some(AggregateFunction::Min),
+            "MAX" => Some(AggregateFunction::Max),
+            _ => None,
+        }
+    }
 
-        let where_clause = if self.match_token(SQLTokenTypes::Where) {
-            Some(self.where_clause()?)
-        } else {
+    fn aggregate_call(&mut self, function: AggregateFunction) -> Result<SelectColumn, String> {
+        let name = self.advance().lexeme.to_uppercase();
+        self.consume(SQLTokenTypes::Leftparen, "Expected '(' after function name")?;
+        let argument = if self.match_token(SQLTokenTypes::Star) {
+            if function != AggregateFunction::Count {
+                return Err(format!("{}(*) is not supported, only COUNT(*)", name));
+            }
             None
+        } else {
+            Some(self.expression()?)
         };
+        self.consume(
+            SQLTokenTypes::Rightparen,
+            "Expected ')' after aggregate argument",
+        )?;
 
-        Ok(SQLStatement::Select(SelectStatement {
-            columns,
-            from,
-            where_clause,
+        Ok(SelectColumn::Aggregate(AggregateCall {
+            function,
+            argument,
         }))
     }
 
+    // ORDER BY price DESC
+    // ORDER BY 1
+    // ORDER BY a, b DESC
+    fn parse_order_by(&mut self, columns: &[SelectColumn]) -> Result<Vec<OrderByItem>, String> {
+        let mut items = Vec::new();
+        loop {
+            let signed = self.check(SQLTokenTypes::Minus);
+            let key = match self.expression()? {
+                // A bare integer refers to a projected column by its 1-based position;
+                // decimals such as 1.0 and signed numbers such as -1 are ordinary
+                // constant expressions.
+                Expression::Literal(Literal::Integer(number)) if !signed => {
+                    let position = usize::try_from(number).unwrap_or(0);
+                    let projected = !columns.contains(&SelectColumn::All);
+                    if position == 0 || (projected && position > columns.len()) {
+                        return Err(format!(
+                            "ORDER BY position {} is not in the select list",
+                            number
+                        ));
+                    }
+                    OrderKey::Position(position)
+                }
+                expression => OrderKey::Expression(expression),
+            };
+
+            let descending = match self.match_any(&[SQLTokenTypes::Asc, SQLTokenTypes::Desc]) {
+                Some(token) => token.token_type == SQLTokenTypes::Desc,
+                None => false,
+            };
+            items.push(OrderByItem { key, descending });
+
+            if !self.match_token(SQLTokenTypes::Comma) {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
     fn insert_statement(&mut self) -> Result<SQLStatement, String> {
         self.consume(SQLTokenTypes::Insert, "Expect INSERT")?;
         self.consume(SQLTokenTypes::Into, "Expect INTO after INSERT")?;
         let table = self
             .consume(SQLTokenTypes::Identifier, "Expect table name")?
             .lexeme
-            .clone();
+            .to_string();
 
         let columns = if self.match_token(SQLTokenTypes::Leftparen) {
             self.parse_column_list()?
@@ -94,14 +404,40 @@ impl Parser {
         };
 
         self.consume(SQLTokenTypes::Values, "Expect VALUES")?;
-        self.consume(SQLTokenTypes::Leftparen, "Expect ( after VALUES")?;
-        let values = self.parse_expression_list()?;
-        self.consume(SQLTokenTypes::Rightparen, "Expect ) after values")?;
+        let mut values: Vec<Vec<Expression>> = Vec::new();
+        loop {
+            self.consume(SQLTokenTypes::Leftparen, "Expect ( before values")?;
+            let row = self.parse_expression_list()?;
+            self.consume(SQLTokenTypes::Rightparen, "Expect ) after values")?;
+
+            // Every tuple must have the arity of the column list, or of the first tuple without one.
+            let expected = match (columns.len(), values.first()) {
+                (0, Some(first)) => Some(first.len()),
+                (0, None) => None,
+                (count, _) => Some(count),
+            };
+            if let Some(expected) = expected {
+                if row.len() != expected {
+                    return Err(format!(
+                        "VALUES tuple {} has {} values, expected {}",
+                        values.len() + 1,
+                        row.len(),
+                        expected
+                    ));
+                }
+            }
+            values.push(row);
+
+            if !self.match_token(SQLTokenTypes::Comma) {
+                break;
+            }
+        }
 
         Ok(SQLStatement::Insert(InsertStatement {
             table,
             columns,
             values,
+            returning: self.parse_returning()?,
         }))
     }
 
@@ -110,7 +446,7 @@ impl Parser {
         let table = self
             .consume(SQLTokenTypes::Identifier, "Expect table name")?
             .lexeme
-            .clone();
+            .to_string();
         self.consume(SQLTokenTypes::Set, "Expect SET after table name")?;
 
         let assignments = self.parse_assignments()?;
@@ -124,6 +460,7 @@ impl Parser {
             table,
             assignments,
             where_clause,
+            returning: self.parse_returning()?,
         }))
     }
 
@@ -133,7 +470,7 @@ impl Parser {
         let table = self
             .consume(SQLTokenTypes::Identifier, "Expect table name")?
             .lexeme
-            .clone();
+            .to_string();
 
         let where_clause = if self.match_token(SQLTokenTypes::Where) {
             Some(self.where_clause()?)
@@ -144,25 +481,96 @@ impl Parser {
         Ok(SQLStatement::Delete(ast::DeleteStatement {
             table,
             where_clause,
+            returning: self.parse_returning()?,
         }))
     }
 
+    // RETURNING id, name
+    // RETURNING *
+    fn parse_returning(&mut self) -> Result<Option<Vec<SelectColumn>>, String> {
+        match self.match_token(SQLTokenTypes::Returning) {
+            true => {
+                let columns = self.parse_select_list()?;
+                if columns
+                    .iter()
+                    .any(|column| matches!(column, SelectColumn::Aggregate(_)))
+                {
+                    return Err("aggregate functions are not allowed in RETURNING".to_string());
+                }
+                Ok(Some(columns))
+            }
+            false => Ok(None),
+        }
+    }
+
     fn create_statement(&mut self) -> Result<SQLStatement, String> {
         self.consume(SQLTokenTypes::Create, "Expect CREATE")?;
         self.consume(SQLTokenTypes::Table, "Expect TABLE after CREATE")?;
         let table = self
             .consume(SQLTokenTypes::Identifier, "Expect table name")?
             .lexeme
-            .clone();
+            .to_string();
 
         self.consume(SQLTokenTypes::Leftparen, "Expect ( after table name")?;
-        let columns = self.parse_column_definitions()?;
+        let mut columns = Vec::new();
+        let mut table_constraints = Vec::new();
+        loop {
+            if self.check_any(&[
+                SQLTokenTypes::Primary,
+                SQLTokenTypes::Unique,
+                SQLTokenTypes::Foreign,
+            ]) {
+                table_constraints.push(self.parse_table_constraint()?);
+            } else {
+                columns.push(self.parse_column_definition()?);
+            }
+            if !self.match_token(SQLTokenTypes::Comma) {
+                break;
+            }
+        }
         self.consume(
             SQLTokenTypes::Rightparen,
             "Expect ) after column definitions",
         )?;
 
-        Ok(SQLStatement::Create(CreateStatement { table, columns }))
+        if columns.is_empty() {
+            return Err("Expect at least one column definition".to_string());
+        }
+
+        // A table has at most one primary key, declared on a column or on the table.
+        let primary_keys = columns
+            .iter()
+            .flat_map(|column| &column.constraints)
+            .filter(|constraint| **constraint == ColumnConstraint::PrimaryKey)
+            .count()
+            + table_constraints
+                .iter()
+                .filter(|constraint| matches!(constraint, TableConstraint::PrimaryKey(_)))
+                .count();
+        if primary_keys > 1 {
+            return Err(format!("Table {} has more than one PRIMARY KEY", table));
+        }
+
+        // Table constraints may only name columns declared in this table.
+        for constraint in &table_constraints {
+            let (kind, names) = match constraint {
+                TableConstraint::PrimaryKey(names) => ("PRIMARY KEY", names),
+                TableConstraint::Unique(names) => ("UNIQUE", names),
+                TableConstraint::ForeignKey { columns: names, .. } => ("FOREIGN KEY", names),
+            };
+            if let Some(unknown) = names
+                .iter()
+                .find(|name| !columns.iter().any(|column| &column.name == *name))
+            {
+                return Err(format!("{} references unknown column {}", kind, unknown));
+            }
+        }
+
+        Ok(SQLStatement::Create(CreateStatement {
+            table,
+            columns,
+            table_constraints,
+        }))
     }
 
     fn drop_statement(&mut self) -> Result<SQLStatement, String> {
@@ -171,11 +579,163 @@ impl Parser {
         let table = self
             .consume(SQLTokenTypes::Identifier, "Expect table name")?
             .lexeme
-            .clone();
+            .to_string();
 
         Ok(SQLStatement::Drop(DropStatement { table }))
     }
 
+    // VACUUM t
+    fn vacuum_statement(&mut self) -> Result<SQLStatement, String> {
+        self.consume(SQLTokenTypes::Vacuum, "Expect VACUUM")?;
+        let table = self
+            .consume(SQLTokenTypes::Identifier, "Expect table name after VACUUM")?
+            .lexeme
+            .to_string();
+
+        Ok(SQLStatement::Vacuum(VacuumStatement { table }))
+    }
+
+    // ALTER TABLE t RENAME TO t2
+    // ALTER TABLE t RENAME [COLUMN] a TO b
+    fn alter_statement(&mut self) -> Result<SQLStatement, String> {
+        self.consume(SQLTokenTypes::Alter, "Expect ALTER")?;
+        self.consume(SQLTokenTypes::Table, "Expect TABLE after ALTER")?;
+        let table = self
+            .consume(SQLTokenTypes::Identifier, "Expect table name")?
+            .lexeme
+            .to_string();
+        self.consume(SQLTokenTypes::Rename, "Expect RENAME after table name")?;
+
+        let action = if self.match_token(SQLTokenTypes::To) {
+            AlterAction::RenameTable(
+                self.consume(SQLTokenTypes::Identifier, "Expect new table name")?
+                    .lexeme
+                    .to_string(),
+            )
+        } else {
+            // COLUMN is not reserved, so it is only a keyword when a column name follows.
+            if self.peek().lexeme.eq_ignore_ascii_case("COLUMN")
+                && self.check_next(SQLTokenTypes::Identifier)
+            {
+                self.advance();
+            }
+            let from = self
+                .consume(
+                    SQLTokenTypes::Identifier,
+                    "Expect TO or column name after RENAME",
+                )?
+                .lexeme
+                .to_string();
+            self.consume(SQLTokenTypes::To, "Expect TO after column name")?;
+            let to = self
+                .consume(SQLTokenTypes::Identifier, "Expect new column name")?
+                .lexeme
+                .to_string();
+            AlterAction::RenameColumn { from, to }
+        };
+
+        Ok(SQLStatement::Alter(AlterStatement { table, action }))
+    }
+
+    // COMMENT ON TABLE t IS 'text'
+    // COMMENT ON COLUMN t.c IS 'text'
+    // COMMENT ON COLUMN t.c IS NULL
+    fn comment_statement(&mut self) -> Result<SQLStatement, String> {
+        self.advance();
+        self.consume(SQLTokenTypes::On, "Expect ON after COMMENT")?;
+
+        let target = if self.match_token(SQLTokenTypes::Table) {
+            CommentTarget::Table(
+                self.consume(SQLTokenTypes::Identifier, "Expect table name")?
+                    .lexeme
+                    .to_string(),
+            )
+        } else if self.peek().lexeme.eq_ignore_ascii_case("COLUMN") {
+            self.advance();
+            let table = self
+                .consume(SQLTokenTypes::Identifier, "Expect table name")?
+                .lexeme
+                .to_string();
+            self.consume(SQLTokenTypes::Dot, "Expect . between table and column name")?;
+            let column = self
+                .consume(SQLTokenTypes::Identifier, "Expect column name")?
+                .lexeme
+                .to_string();
+            CommentTarget::Column { table, column }
+        } else {
+            return Err("Expect TABLE or COLUMN after COMMENT ON".to_string());
+        };
+
+        self.consume(SQLTokenTypes::IS, "Expect IS after comment target")?;
+        let comment = if self.match_token(SQLTokenTypes::Null) {
+            None
+        } else {
+            match self.literal()? {
+                Literal::String(text) => Some(text),
+                other => return Err(format!("Comment must be a string, found {:?}", other)),
+            }
+        };
+
+        Ok(SQLStatement::Comment(CommentStatement { target, comment }))
+    }
+
+    fn explain_statement(&mut self) -> Result<SQLStatement, String> {
+        self.consume(SQLTokenTypes::Explain, "Expect EXPLAIN")?;
+        if !self.check(SQLTokenTypes::Select) {
+            return Err("Expect SELECT after EXPLAIN".to_string());
+        }
+
+        Ok(SQLStatement::Explain(Box::new(self.parse_select()?)))
+    }
+
+    // BEGIN [TRANSACTION]
+    // COMMIT
+    // ROLLBACK
+    fn transaction_statement(&mut self) -> Result<SQLStatement, String> {
+        let statement = match self.advance().token_type {
+            SQLTokenTypes::Begin => {
+                // TRANSACTION is not reserved, so it is matched by its text.
+                if self.check(SQLTokenTypes::Identifier)
+                    && self.peek().lexeme.eq_ignore_ascii_case("TRANSACTION")
+                {
+                    self.advance();
+                }
+                TransactionStatement::Begin
+            }
+            SQLTokenTypes::Commit => TransactionStatement::Commit,
+            _ => TransactionStatement::Rollback,
+        };
+
+        Ok(SQLStatement::Transaction(statement))
+    }
+
+    // SAVEPOINT name
+    // ROLLBACK TO [SAVEPOINT] name
+    // RELEASE [SAVEPOINT] name
+    fn savepoint_statement(&mut self) -> Result<SQLStatement, String> {
+        let statement = match self.advance().token_type {
+            SQLTokenTypes::Savepoint => SavepointStatement::Define(self.savepoint_name()?),
+            SQLTokenTypes::Rollback => {
+                self.consume(SQLTokenTypes::To, "Expect TO after ROLLBACK")?;
+                self.match_token(SQLTokenTypes::Savepoint);
+                SavepointStatement::RollbackTo(self.savepoint_name()?)
+            }
+            _ => {
+                self.match_token(SQLTokenTypes::Savepoint);
+                SavepointStatement::Release(self.savepoint_name()?)
+            }
+        };
+
+        Ok(SQLStatement::Savepoint(statement))
+    }
+
+    fn savepoint_name(&mut self) -> Result<String, String> {
+        Ok(self
+            .consume(SQLTokenTypes::Identifier, "Expect savepoint name")?
+            .lexeme
+            .to_string())
+    }
+
     // The entry point for parsing the WHERE clause
     // WHERE foo = 'bar'
     // WHERE foo = 'bar' AND fuzz = 'fuzz0'
@@ -225,14 +785,22 @@ impl Parser {
         if self.check(SQLTokenTypes::Not) {
             // Handle NOT operator
             self.consume(SQLTokenTypes::Not, "Expected 'NOT' operator")?;
-            let condition = self.parse_primary_condition()?; // Recursively parse the condition after NOT
+            let condition = self.nested(Self::parse_primary_condition)?; // Recursively parse the condition after NOT
             return Ok(Condition::Not(Box::new(condition)));
         }
 
+        // `(a, b)` opens a row value rather than a grouped condition.
+        if self.check(SQLTokenTypes::Leftparen)
+            && self.check_at(1, SQLTokenTypes::Identifier)
+            && self.check_at(2, SQLTokenTypes::Comma)
+        {
+            return self.parse_tuple_in_condition();
+        }
+
         if self.check(SQLTokenTypes::Leftparen) {
             // Handle grouped conditions or subqueries.
             self.consume(SQLTokenTypes::Leftparen, "Expected '('")?;
-            let condition = self.parse_or_condition()?;
+            let condition = self.nested(Self::parse_or_condition)?;
             self.consume(SQLTokenTypes::Rightparen, "Expected ')'")?;
             return Ok(condition);
         }
@@ -242,16 +810,18 @@ impl Parser {
 
     fn parse_comparison_condition(&mut self) -> Result<Condition, String> {
         if self.check(SQLTokenTypes::Identifier) {
-            let left = self.peek().lexeme.clone();
+            let left = self.peek().lexeme.to_string();
             self.consume(SQLTokenTypes::Identifier, "expected an identifier")?;
 
-            if self.check(SQLTokenTypes::Equal)
-                || self.check(SQLTokenTypes::GreaterThanOrEqualTo)
-                || self.check(SQLTokenTypes::LesserThanOrEqualTo)
-                || self.check(SQLTokenTypes::Lesser)
-                || self.check(SQLTokenTypes::Greater)
-            {
-                let operator = match self.peek().token_type {
+            if let Some(token) = self.match_any(&[
+                SQLTokenTypes::Equal,
+                SQLTokenTypes::NotEqual,
+                SQLTokenTypes::GreaterThanOrEqualTo,
+                SQLTokenTypes::LesserThanOrEqualTo,
+                SQLTokenTypes::Lesser,
+                SQLTokenTypes::Greater,
+            ]) {
+                let operator = match token.token_type {
                     SQLTokenTypes::NotEqual => ComparisonOperator::NotEqual,
                     SQLTokenTypes::Equal => ComparisonOperator::Equal,
                     SQLTokenTypes::GreaterThanOrEqualTo => ComparisonOperator::GreaterThanOrEqual,
@@ -265,11 +835,6 @@ impl Parser {
                     }
                 };
 
-                self.consume(
-                    self.peek().token_type.clone(),
-                    "expected comparison operator",
-                )?;
-
                 // Ensure the right-hand side is a valid literal (string, number, or boolean).
                 let right = self.expression()?;
 
@@ -278,29 +843,66 @@ impl Parser {
                     left: Expression::Identifier(left),
                     right,
                 }));
+            } else if self.check(SQLTokenTypes::In) {
+                return self.parse_in_condition(left);
+            } else if self.check(SQLTokenTypes::Like)
+                || (self.check(SQLTokenTypes::Not) && self.check_next(SQLTokenTypes::Like))
+            {
+                return self.parse_like_condition(left);
+            } else if self.check(SQLTokenTypes::Between)
+                || (self.check(SQLTokenTypes::Not) && self.check_next(SQLTokenTypes::Between))
+            {
+                return self.parse_between_condition(left);
             } else if self.check(SQLTokenTypes::Null)
                 || self.check(SQLTokenTypes::IS)
                 || self.check(SQLTokenTypes::Not)
             {
                 if self.check(SQLTokenTypes::IS) {
                     self.consume(SQLTokenTypes::IS, "expected IS operator")?;
-                    if self.check(SQLTokenTypes::Not) {
-                        self.consume(SQLTokenTypes::Not, "expected NOT operator")?;
-                        if self.check(SQLTokenTypes::Null) {
-                            self.consume(SQLTokenTypes::Null, "expected NULL operator")?;
-                            return Ok(Condition::NullCheck(NullCheckCondition::IsNotNull {
-                                identifier: left,
-                            }));
-                        }
-                    }
+                    let negated = self.match_token(SQLTokenTypes::Not);
                     if self.check(SQLTokenTypes::Null) {
                         self.consume(SQLTokenTypes::Null, "expected NULL operator")?;
-                        return Ok(Condition::NullCheck(NullCheckCondition::IsNull {
-                            identifier: left,
+                        return Ok(Condition::NullCheck(match negated {
+                            true => NullCheckCondition::IsNotNull { identifier: left },
+                            false => NullCheckCondition::IsNull { identifier: left },
+                        }));
+                    }
+
+                    // IS [NOT] TRUE / IS [NOT] FALSE never yield NULL, so a NULL
+                    // operand must compare like IS [NOT] DISTINCT FROM, not = / <>.
+                    if self.check(SQLTokenTypes::True) || self.check(SQLTokenTypes::False) {
+                        let value = self.advance().token_type == SQLTokenTypes::True;
+                        return Ok(Condition::Comparison(ComparisonCondition {
+                            operator: match negated {
+                                true => ComparisonOperator::IsDistinctFrom,
+                                false => ComparisonOperator::IsNotDistinctFrom,
+                            },
+                            left: Expression::Identifier(left),
+                            right: Expression::Literal(Literal::Boolean(value)),
+                        }));
+                    }
+
+                    // IS [NOT] DISTINCT FROM treats NULL as an ordinary, comparable value.
+                    if self.match_token(SQLTokenTypes::Distinct) {
+                        self.consume(SQLTokenTypes::From, "expected FROM after IS DISTINCT")?;
+                        // Against NULL this is exactly IS [NOT] NULL.
+                        if self.match_token(SQLTokenTypes::Null) {
+                            return Ok(Condition::NullCheck(match negated {
+                                true => NullCheckCondition::IsNull { identifier: left },
+                                false => NullCheckCondition::IsNotNull { identifier: left },
+                            }));
+                        }
+                        return Ok(Condition::Comparison(ComparisonCondition {
+                            operator: match negated {
+                                true => ComparisonOperator::IsNotDistinctFrom,
+                                false => ComparisonOperator::IsDistinctFrom,
+                            },
+                            left: Expression::Identifier(left),
+                            right: self.expression()?,
                         }));
                     }
 
-                    return Err("unexpected token found".to_string());
+                    return Err("expected NULL, TRUE, FALSE or DISTINCT FROM after IS".to_string());
                 }
                 return Err("unexpected token found".to_string());
             } else {
@@ -316,35 +918,354 @@ impl Parser {
         Err("Expected identifier on the left-hand side of the comparison.".to_string())
     }
 
-    fn expression(&mut self) -> Result<Expression, String> {
-        if self.check(SQLTokenTypes::Identifier) {
-            Ok(Expression::Identifier(self.advance().lexeme.clone()))
-        } else if self.check(SQLTokenTypes::String) {
-            Ok(Expression::Literal(Literal::String(
-                self.advance().lexeme.clone(),
-            )))
-        } else if self.check(SQLTokenTypes::Number) {
-            let number: f64 = self
-                .advance()
-                .lexeme
-                .parse()
-                .map_err(|_| "Invalid number".to_string())?;
-            Ok(Expression::Literal(Literal::Number(number)))
+    // foo IN ('bar', 'baz')
+    // foo IN (SELECT bar FROM baz)
+    fn parse_in_condition(&mut self, identifier: String) -> Result<Condition, String> {
+        self.consume(SQLTokenTypes::In, "expected IN operator")?;
+        self.consume(SQLTokenTypes::Leftparen, "Expected '(' after IN")?;
+
+        let values = if self.check(SQLTokenTypes::Select) {
+            let subquery = self.nested(Self::parse_select)?;
+            if subquery.for_update {
+                return Err("FOR UPDATE is not allowed in a subquery".to_string());
+            }
+            InValues::Subquery(Box::new(subquery))
         } else {
-            Err("Expected expression".to_string())
-        }
+            let mut values: Vec<Expression> = Vec::new();
+            loop {
+                let value = self.in_value()?;
+                // Every literal in the list must share the type of the first one.
+                if let (Some(first), Expression::Literal(literal)) =
+                    (first_literal(values.iter()), &value)
+                {
+                    if !first.same_type(literal) {
+                        return Err(format!(
+                            "IN list values must all have the same type, found {:?} and {:?}",
+                            first, literal
+                        ));
+                    }
+                }
+                values.push(value);
+                if !self.match_token(SQLTokenTypes::Comma) {
+                    break;
+                }
+            }
+            InValues::List(values)
+        };
+
+        self.consume(SQLTokenTypes::Rightparen, "Expected ')' after IN values")?;
+        Ok(Condition::In(InCondition { identifier, values }))
     }
 
-    fn parse_column_list(&mut self) -> Result<Vec<String>, String> {
-        let mut columns = Vec::new();
-        loop {
-            columns.push(
-                self.consume(SQLTokenTypes::Identifier, "Expect column name")?
-                    .lexeme
-                    .clone(),
-            );
-            if !self.match_token(SQLTokenTypes::Comma) {
-                break;
+    // name LIKE 'A%'
+    // name NOT LIKE '%z'
+    // discount LIKE '10!%' ESCAPE '!'
+    // path LIKE 'a\%' ESCAPE '\\'  (a lone '\' would escape the closing quote)
+    fn parse_like_condition(&mut self, identifier: String) -> Result<Condition, String> {
+        let negated = self.match_token(SQLTokenTypes::Not);
+        self.consume(SQLTokenTypes::Like, "expected LIKE operator")?;
+        let pattern = match self.literal()? {
+            Literal::String(pattern) => pattern,
+            other => return Err(format!("LIKE pattern must be a string, found {:?}", other)),
+        };
+
+        let escape = if self.match_token(SQLTokenTypes::Escape) {
+            let escape = match self.literal()? {
+                Literal::String(escape) => escape,
+                other => return Err(format!("ESCAPE must be a string, found {:?}", other)),
+            };
+            let mut chars = escape.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => return Err("ESCAPE must be a single character".to_string()),
+            }
+        } else {
+            None
+        };
+
+        // The escape character must quote something.
+        if let Some(escape) = escape {
+            let mut chars = pattern.chars();
+            while let Some(c) = chars.next() {
+                if c == escape && chars.next().is_none() {
+                    return Err("LIKE pattern must not end with the escape character".to_string());
+                }
+            }
+        }
+
+        Ok(Condition::Like(LikeCondition {
+            identifier,
+            pattern,
+            escape,
+            negated,
+        }))
+    }
+
+    // age BETWEEN 10 AND 20
+    // age NOT BETWEEN 10 AND 20
+    fn parse_between_condition(&mut self, identifier: String) -> Result<Condition, String> {
+        let negated = self.match_token(SQLTokenTypes::Not);
+        self.consume(SQLTokenTypes::Between, "expected BETWEEN operator")?;
+        let low = self.expression()?;
+        self.consume(
+            SQLTokenTypes::And,
+            "Expected AND between the BETWEEN bounds",
+        )?;
+        let high = self.expression()?;
+
+        Ok(Condition::Between(BetweenCondition {
+            identifier,
+            low,
+            high,
+            negated,
+        }))
+    }
+
+    // (a, b) IN ((1, 2), (3, 4))
+    fn parse_tuple_in_condition(&mut self) -> Result<Condition, String> {
+        self.consume(SQLTokenTypes::Leftparen, "Expected '('")?;
+        let columns = self.parse_column_list()?;
+        self.consume(SQLTokenTypes::In, "Expected IN after row value")?;
+        self.consume(SQLTokenTypes::Leftparen, "Expected '(' after IN")?;
+
+        let mut rows: Vec<Vec<Expression>> = Vec::new();
+        loop {
+            self.consume(SQLTokenTypes::Leftparen, "Expected '(' to start an IN row")?;
+            let mut row = Vec::new();
+            loop {
+                row.push(self.in_value()?);
+                if !self.match_token(SQLTokenTypes::Comma) {
+                    break;
+                }
+            }
+            self.consume(SQLTokenTypes::Rightparen, "Expected ')' after IN row")?;
+
+            if row.len() != columns.len() {
+                return Err(format!(
+                    "IN row {} has {} values, expected {}",
+                    rows.len() + 1,
+                    row.len(),
+                    columns.len()
+                ));
+            }
+            // Each position must keep the type of the first literal in that column.
+            for (index, value) in row.iter().enumerate() {
+                let Expression::Literal(literal) = value else {
+                    continue;
+                };
+                if let Some(expected) = first_literal(rows.iter().map(|row| &row[index])) {
+                    if !expected.same_type(literal) {
+                        return Err(format!(
+                            "IN row values must keep the same type per column, found {:?} and {:?}",
+                            expected, literal
+                        ));
+                    }
+                }
+            }
+            rows.push(row);
+
+            if !self.match_token(SQLTokenTypes::Comma) {
+                break;
+            }
+        }
+
+        self.consume(SQLTokenTypes::Rightparen, "Expected ')' after IN rows")?;
+        Ok(Condition::TupleIn(TupleInCondition { columns, rows }))
+    }
+
+    // A literal or `?` placeholder inside an IN list.
+    fn in_value(&mut self) -> Result<Expression, String> {
+        match self.unary()? {
+            value @ (Expression::Literal(_) | Expression::Parameter(_)) => Ok(value),
+            other => Err(format!("Expected literal or parameter, found {:?}", other)),
+        }
+    }
+
+    fn literal(&mut self) -> Result<Literal, String> {
+        match self.unary()? {
+            Expression::Literal(literal) => Ok(literal),
+            other => Err(format!("Expected literal, found {:?}", other)),
+        }
+    }
+
+    // || binds loosest, then + and -, then * and /
+    // first_name || ' ' || last_name
+    // price * 2 + 1
+    // (price + tax) / 2
+    fn expression(&mut self) -> Result<Expression, String> {
+        let mut left = self.additive()?;
+        while self.match_token(SQLTokenTypes::Concat) {
+            let right = self.additive()?;
+            left = Expression::Concat(ConcatExpression {
+                left: Box::new(left),
+                right: Box::new(right),
+            });
+        }
+
+        Ok(left)
+    }
+
+    fn additive(&mut self) -> Result<Expression, String> {
+        let mut left = self.term()?;
+        while self.check(SQLTokenTypes::Plus) || self.check(SQLTokenTypes::Minus) {
+            let operator = match self.advance().token_type {
+                SQLTokenTypes::Plus => ArithmeticOperator::Add,
+                _ => ArithmeticOperator::Subtract,
+            };
+            let right = self.term()?;
+            left = Expression::Arithmetic(ArithmeticExpression {
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+            });
+        }
+
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expression, String> {
+        let mut left = self.unary()?;
+        while self.check(SQLTokenTypes::Star) || self.check(SQLTokenTypes::Slash) {
+            let operator = match self.advance().token_type {
+                SQLTokenTypes::Star => ArithmeticOperator::Multiply,
+                _ => ArithmeticOperator::Divide,
+            };
+            let right = self.unary()?;
+            left = Expression::Arithmetic(ArithmeticExpression {
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+            });
+        }
+
+        Ok(left)
+    }
+
+    // -1
+    // -(price + tax)
+    fn unary(&mut self) -> Result<Expression, String> {
+        if !self.match_token(SQLTokenTypes::Minus) {
+            return self.primary_expression();
+        }
+
+        // i64::MIN only fits once negated, so its magnitude is scanned as a u64.
+        if self.check(SQLTokenTypes::Number)
+            && self
+                .peek()
+                .literal
+                .as_ref()
+                .is_some_and(|literal| literal.is::<u64>())
+        {
+            self.advance();
+            return Ok(Expression::Literal(Literal::Integer(i64::MIN)));
+        }
+
+        Ok(match self.nested(Self::unary)? {
+            Expression::Literal(Literal::Integer(integer)) if integer != i64::MIN => {
+                Expression::Literal(Literal::Integer(-integer))
+            }
+            Expression::Literal(Literal::Number(number)) => {
+                Expression::Literal(Literal::Number(-number))
+            }
+            operand => Expression::Negate(Box::new(operand)),
+        })
+    }
+
+    fn primary_expression(&mut self) -> Result<Expression, String> {
+        if self.match_token(SQLTokenTypes::Leftparen) {
+            let expression = self.nested(Self::expression)?;
+            self.consume(SQLTokenTypes::Rightparen, "Expected ')' after expression")?;
+            Ok(expression)
+        } else if self.match_token(SQLTokenTypes::Parameter) {
+            // Placeholders are numbered in order of appearance, starting at 0.
+            self.parameter_count += 1;
+            Ok(Expression::Parameter(self.parameter_count - 1))
+        } else if self.check(SQLTokenTypes::Identifier) && self.check_next(SQLTokenTypes::Leftparen)
+        {
+            self.nested(Self::function_call)
+        } else if self.check(SQLTokenTypes::Identifier) {
+            Ok(Expression::Identifier(self.advance().lexeme.to_string()))
+        } else if self.check(SQLTokenTypes::String) {
+            let value = self
+                .advance()
+                .literal
+                .as_ref()
+                .and_then(|literal| literal.downcast_ref::<String>())
+                .cloned()
+                .ok_or_else(|| "Invalid string literal".to_string())?;
+            Ok(Expression::Literal(Literal::String(value)))
+        } else if self.check(SQLTokenTypes::True) || self.check(SQLTokenTypes::False) {
+            let value = self.advance().token_type == SQLTokenTypes::True;
+            Ok(Expression::Literal(Literal::Boolean(value)))
+        } else if self.check(SQLTokenTypes::Number) {
+            let literal = self.advance().literal.as_ref();
+            if let Some(integer) = literal.and_then(|literal| literal.downcast_ref::<i64>()) {
+                Ok(Expression::Literal(Literal::Integer(*integer)))
+            } else if let Some(number) = literal.and_then(|literal| literal.downcast_ref::<f64>()) {
+                Ok(Expression::Literal(Literal::Number(*number)))
+            } else if literal.is_some_and(|literal| literal.is::<u64>()) {
+                Err(format!(
+                    "integer literal {} is out of range",
+                    self.previous().lexeme
+                ))
+            } else {
+                Err("Invalid number".to_string())
+            }
+        } else {
+            Err("Expected expression".to_string())
+        }
+    }
+
+    // UPPER(name), LOWER(name), LENGTH(name), SUBSTR(name, start[, length])
+    fn function_call(&mut self) -> Result<Expression, String> {
+        let name = self.advance().lexeme.to_uppercase();
+        let (function, min_arguments, max_arguments) = match name.as_str() {
+            "UPPER" => (ScalarFunction::Upper, 1, 1),
+            "LOWER" => (ScalarFunction::Lower, 1, 1),
+            "LENGTH" => (ScalarFunction::Length, 1, 1),
+            "SUBSTR" => (ScalarFunction::Substr, 2, 3),
+            _ => return Err(format!("Unknown function: {}", name)),
+        };
+
+        self.consume(SQLTokenTypes::Leftparen, "Expected '(' after function name")?;
+        let arguments = if self.check(SQLTokenTypes::Rightparen) {
+            Vec::new()
+        } else {
+            self.parse_expression_list()?
+        };
+        self.consume(
+            SQLTokenTypes::Rightparen,
+            "Expected ')' after function arguments",
+        )?;
+
+        if arguments.len() < min_arguments || arguments.len() > max_arguments {
+            return Err(format!(
+                "{} expects {} arguments, found {}",
+                name,
+                match min_arguments == max_arguments {
+                    true => min_arguments.to_string(),
+                    false => format!("{} to {}", min_arguments, max_arguments),
+                },
+                arguments.len()
+            ));
+        }
+
+        Ok(Expression::Function(FunctionCall {
+            function,
+            arguments,
+        }))
+    }
+
+    fn parse_column_list(&mut self) -> Result<Vec<String>, String> {
+        let mut columns = Vec::new();
+        loop {
+            columns.push(
+                self.consume(SQLTokenTypes::Identifier, "Expect column name")?
+                    .lexeme
+                    .to_string(),
+            );
+            if !self.match_token(SQLTokenTypes::Comma) {
+                break;
             }
         }
         self.consume(SQLTokenTypes::Rightparen, "Expect ) after column list")?;
@@ -368,7 +1289,7 @@ impl Parser {
             let column = self
                 .consume(SQLTokenTypes::Identifier, "Expect column name")?
                 .lexeme
-                .clone();
+                .to_string();
             self.consume(SQLTokenTypes::Equal, "Expect = after column name")?;
             let value = self.expression()?;
             assignments.push(Assignment { column, value });
@@ -379,25 +1300,86 @@ impl Parser {
         Ok(assignments)
     }
 
-    fn parse_column_definitions(&mut self) -> Result<Vec<ColumnDefinition>, String> {
-        let mut columns = Vec::new();
-        loop {
-            let name = self
-                .consume(SQLTokenTypes::Identifier, "Expect column name")?
-                .lexeme
-                .clone();
-            let data_type = self.parse_data_type()?;
-            let constraints = self.parse_column_constraints()?;
-            columns.push(ColumnDefinition {
-                name,
-                data_type,
-                constraints,
-            });
-            if !self.match_token(SQLTokenTypes::Comma) {
-                break;
+    fn parse_column_definition(&mut self) -> Result<ColumnDefinition, String> {
+        let name = self
+            .consume(SQLTokenTypes::Identifier, "Expect column name")?
+            .lexeme
+            .to_string();
+        // SERIAL is shorthand for INTEGER AUTOINCREMENT.
+        let serial = self.check(SQLTokenTypes::Identifier)
+            && self.peek().lexeme.eq_ignore_ascii_case("SERIAL");
+        let data_type = match serial {
+            true => {
+                self.advance();
+                DataType::Integer
+            }
+            false => self.parse_data_type()?,
+        };
+        let mut constraints = self.parse_column_constraints()?;
+        if serial {
+            constraints.insert(0, ColumnConstraint::AutoIncrement);
+        }
+
+        let auto_increments = constraints
+            .iter()
+            .filter(|constraint| **constraint == ColumnConstraint::AutoIncrement)
+            .count();
+        if auto_increments > 1 {
+            return Err(format!("Column {} is AUTOINCREMENT more than once", name));
+        }
+        if auto_increments == 1 && data_type != DataType::Integer {
+            return Err(format!("AUTOINCREMENT column {} must be INTEGER", name));
+        }
+
+        Ok(ColumnDefinition {
+            name,
+            data_type,
+            constraints,
+        })
+    }
+
+    // Table level constraints appear in the column list as pseudo-columns
+    // PRIMARY KEY (a, b)
+    // UNIQUE (x, y)
+    // FOREIGN KEY (a) REFERENCES other (b) ON DELETE CASCADE
+    fn parse_table_constraint(&mut self) -> Result<TableConstraint, String> {
+        let kind = self
+            .expect_one_of(
+                &[
+                    SQLTokenTypes::Primary,
+                    SQLTokenTypes::Unique,
+                    SQLTokenTypes::Foreign,
+                ],
+                "Expect PRIMARY KEY, UNIQUE or FOREIGN KEY",
+            )?
+            .token_type
+            .clone();
+
+        match kind {
+            SQLTokenTypes::Primary => {
+                self.consume(SQLTokenTypes::Key, "Expect KEY after PRIMARY")?;
+                self.consume(SQLTokenTypes::Leftparen, "Expect ( after PRIMARY KEY")?;
+                Ok(TableConstraint::PrimaryKey(self.parse_column_list()?))
+            }
+            SQLTokenTypes::Foreign => {
+                self.consume(SQLTokenTypes::Key, "Expect KEY after FOREIGN")?;
+                self.consume(SQLTokenTypes::Leftparen, "Expect ( after FOREIGN KEY")?;
+                let columns = self.parse_column_list()?;
+                self.consume(
+                    SQLTokenTypes::References,
+                    "Expect REFERENCES after FOREIGN KEY columns",
+                )?;
+                let references = self.parse_foreign_key_reference()?;
+                Ok(TableConstraint::ForeignKey {
+                    columns,
+                    references,
+                })
+            }
+            _ => {
+                self.consume(SQLTokenTypes::Leftparen, "Expect ( after UNIQUE")?;
+                Ok(TableConstraint::Unique(self.parse_column_list()?))
             }
         }
-        Ok(columns)
     }
 
     fn parse_data_type(&mut self) -> Result<DataType, String> {
@@ -408,29 +1390,46 @@ impl Parser {
         match type_name.as_str() {
             "INTEGER" => Ok(DataType::Integer),
             "FLOAT" => Ok(DataType::Float),
-            "VARCHAR" => {
-                if self.match_token(SQLTokenTypes::Leftparen) {
-                    let size = self
-                        .consume(SQLTokenTypes::Number, "Expect size for VARCHAR")?
-                        .lexeme
-                        .parse()
-                        .map_err(|_| "Invalid VARCHAR size".to_string())?;
-                    self.consume(SQLTokenTypes::Rightparen, "Expect ) after VARCHAR size")?;
-                    Ok(DataType::Varchar(Some(size)))
-                } else {
-                    Ok(DataType::Varchar(None))
-                }
-            }
+            "VARCHAR" => Ok(DataType::Varchar(self.parse_type_length("VARCHAR")?)),
+            "CHAR" => Ok(DataType::Char(self.parse_type_length("CHAR")?)),
+            "TEXT" => Ok(DataType::Text),
             "BOOLEAN" => Ok(DataType::Boolean),
             _ => Err(format!("Unsupported data type: {}", type_name)),
         }
     }
 
+    // Parses the optional `(n)` length suffix of a character type.
+    // The length must be a positive integer literal.
+    fn parse_type_length(&mut self, type_name: &str) -> Result<Option<usize>, String> {
+        if !self.match_token(SQLTokenTypes::Leftparen) {
+            return Ok(None);
+        }
+
+        let size: usize = self
+            .consume(
+                SQLTokenTypes::Number,
+                &format!("Expect size for {}", type_name),
+            )?
+            .lexeme
+            .parse()
+            .map_err(|_| format!("Invalid {} size", type_name))?;
+        if size == 0 {
+            return Err(format!("{} size must be greater than zero", type_name));
+        }
+        self.consume(
+            SQLTokenTypes::Rightparen,
+            &format!("Expect ) after {} size", type_name),
+        )?;
+        Ok(Some(size))
+    }
+
     fn parse_column_constraints(&mut self) -> Result<Vec<ColumnConstraint>, String> {
         let mut constraints = Vec::new();
         while self.match_token(SQLTokenTypes::Primary)
             || self.match_token(SQLTokenTypes::Not)
             || self.match_token(SQLTokenTypes::Unique)
+            || self.match_token(SQLTokenTypes::References)
+            || self.match_token(SQLTokenTypes::Autoincrement)
         {
             match self.previous().token_type {
                 SQLTokenTypes::Primary => {
@@ -441,16 +1440,73 @@ impl Parser {
                     self.consume(SQLTokenTypes::Null, "Expect NULL after NOT")?;
                     constraints.push(ColumnConstraint::NotNull)
                 }
-                SQLTokenTypes::Unique => {
-                    self.consume(SQLTokenTypes::Unique, "Expected Unique constraints")?;
-                    constraints.push(ColumnConstraint::Unique)
-                }
+                SQLTokenTypes::Unique => constraints.push(ColumnConstraint::Unique),
+                SQLTokenTypes::References => constraints.push(ColumnConstraint::References(
+                    self.parse_foreign_key_reference()?,
+                )),
+                SQLTokenTypes::Autoincrement => constraints.push(ColumnConstraint::AutoIncrement),
                 _ => return Err("unknown token found".to_string()),
             }
         }
         Ok(constraints)
     }
 
+    // Parses what follows REFERENCES
+    // other (b)
+    // other (b) ON DELETE SET NULL ON UPDATE RESTRICT
+    fn parse_foreign_key_reference(&mut self) -> Result<ForeignKeyReference, String> {
+        let table = self
+            .consume(
+                SQLTokenTypes::Identifier,
+                "Expect table name after REFERENCES",
+            )?
+            .lexeme
+            .to_string();
+        self.consume(
+            SQLTokenTypes::Leftparen,
+            "Expect ( after referenced table name",
+        )?;
+        let columns = self.parse_column_list()?;
+
+        let mut on_delete = None;
+        let mut on_update = None;
+        while self.match_token(SQLTokenTypes::On) {
+            if self.match_token(SQLTokenTypes::Delete) {
+                if on_delete.is_some() {
+                    return Err("ON DELETE specified more than once".to_string());
+                }
+                on_delete = Some(self.parse_referential_action()?);
+            } else if self.match_token(SQLTokenTypes::Update) {
+                if on_update.is_some() {
+                    return Err("ON UPDATE specified more than once".to_string());
+                }
+                on_update = Some(self.parse_referential_action()?);
+            } else {
+                return Err("Expect DELETE or UPDATE after ON".to_string());
+            }
+        }
+
+        Ok(ForeignKeyReference {
+            table,
+            columns,
+            on_delete,
+            on_update,
+        })
+    }
+
+    fn parse_referential_action(&mut self) -> Result<ReferentialAction, String> {
+        if self.match_token(SQLTokenTypes::Cascade) {
+            Ok(ReferentialAction::Cascade)
+        } else if self.match_token(SQLTokenTypes::Restrict) {
+            Ok(ReferentialAction::Restrict)
+        } else if self.match_token(SQLTokenTypes::Set) {
+            self.consume(SQLTokenTypes::Null, "Expect NULL after SET")?;
+            Ok(ReferentialAction::SetNull)
+        } else {
+            Err("Expect CASCADE, RESTRICT or SET NULL".to_string())
+        }
+    }
+
     fn consume(&mut self, token_type: SQLTokenTypes, message: &str) -> Result<&Token, String> {
         if self.check(token_type) {
             Ok(self.advance())
@@ -459,6 +1515,31 @@ impl Parser {
         }
     }
 
+    // Consumes the current token if it is any of `token_types`.
+    fn match_any(&mut self, token_types: &[SQLTokenTypes]) -> Option<&Token> {
+        if self.check_any(token_types) {
+            Some(self.advance())
+        } else {
+            None
+        }
+    }
+
+    fn expect_one_of(
+        &mut self,
+        token_types: &[SQLTokenTypes],
+        message: &str,
+    ) -> Result<&Token, String> {
+        if self.check_any(token_types) {
+            Ok(self.advance())
+        } else {
+            Err(message.to_string())
+        }
+    }
+
+    fn check_any(&self, token_types: &[SQLTokenTypes]) -> bool {
+        !self.is_at_end() && token_types.contains(&self.peek().token_type)
+    }
+
     fn match_token(&mut self, token_type: SQLTokenTypes) -> bool {
         if self.check(token_type) {
             self.advance();
@@ -468,6 +1549,35 @@ impl Parser {
         }
     }
 
+    // Runs a recursive production one level deeper, failing once max_depth is reached.
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        if self.depth >= self.max_depth {
+            return Err(format!(
+                "max nesting depth exceeded ({} levels)",
+                self.max_depth
+            ));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn check_next(&self, token_type: SQLTokenTypes) -> bool {
+        self.check_at(1, token_type)
+    }
+
+    // Looks `offset` tokens past the current one without consuming anything.
+    fn check_at(&self, offset: usize, token_type: SQLTokenTypes) -> bool {
+        match self.tokens.get(self.current + offset) {
+            Some(token) => token.token_type == token_type,
+            None => false,
+        }
+    }
+
     fn check(&self, token_type: SQLTokenTypes) -> bool {
         if self.is_at_end() {
             false
@@ -496,17 +1606,23 @@ impl Parser {
     }
 }
 
+// Parameters match any type, so IN type checks compare against the first literal.
+fn first_literal<'a>(values: impl Iterator<Item = &'a Expression>) -> Option<&'a Literal> {
+    values
+        .filter_map(|value| match value {
+            Expression::Literal(literal) => Some(literal),
+            _ => None,
+        })
+        .next()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_select_statement() {
-        let mut parser = Parser::new(
-            "SELECT name, age FROM users WHERE NOT(((foo = 'bar' AND fuzz = 'fuzz0') OR (foo = 'baz' AND fuz = 'dazz')) AND (IS_ACTIVE = FALSE AND IS_ENABLED))"
-                .to_string(),
-        );
-        let result = parser.parse();
+        let result = parse("SELECT name, age FROM users WHERE NOT(((foo = 'bar' AND fuzz = 'fuzz0') OR (foo = 'baz' AND fuz = 'dazz')) AND (IS_ACTIVE = FALSE AND IS_ENABLED))");
         if let Ok(SQLStatement::Select(select_stmt)) = result {
             assert_eq!(select_stmt.columns.len(), 2);
             assert_eq!(select_stmt.from, Some("users".to_string()));
@@ -519,72 +1635,1513 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_insert_statement() {
-        let mut parser =
-            Parser::new("INSERT INTO users (name, age) VALUES ('John Doe', 30)".to_string());
-        let result = parser.parse();
-        assert!(result.is_ok());
-        if let Ok(SQLStatement::Insert(insert_stmt)) = result {
-            assert_eq!(insert_stmt.table, "users");
-            assert_eq!(insert_stmt.columns, vec!["name", "age"]);
-            assert_eq!(insert_stmt.values.len(), 2);
-        } else {
-            panic!("Expected Insert statement");
+    fn parse(sql: &str) -> Result<SQLStatement, String> {
+        Parser::new(sql.to_string()).and_then(|mut parser| parser.parse())
+    }
+
+    fn parse_where(sql: &str) -> Condition {
+        match parse(sql) {
+            Ok(SQLStatement::Select(SelectStatement {
+                where_clause: Some(where_clause),
+                ..
+            })) => where_clause.condition,
+            other => panic!("Expected Select with WHERE clause, got {:?}", other),
         }
     }
 
     #[test]
-    fn test_update_statement() {
-        let mut parser =
-            Parser::new("UPDATE users SET age = 31 WHERE name = 'John Doe'".to_string());
-        let result = parser.parse();
-        assert!(result.is_ok());
-        if let Ok(SQLStatement::Update(update_stmt)) = result {
-            assert_eq!(update_stmt.table, "users");
-            assert_eq!(update_stmt.assignments.len(), 1);
-            assert!(update_stmt.where_clause.is_some());
-        } else {
-            panic!("Expected Update statement");
-        }
+    fn test_is_boolean_checks() {
+        assert_eq!(
+            parse_where("SELECT * FROM users WHERE active IS TRUE"),
+            Condition::Comparison(ComparisonCondition {
+                operator: ComparisonOperator::IsNotDistinctFrom,
+                left: Expression::Identifier("active".to_string()),
+                right: Expression::Literal(Literal::Boolean(true)),
+            })
+        );
+        assert_eq!(
+            parse_where("SELECT * FROM users WHERE active IS NOT FALSE"),
+            Condition::Comparison(ComparisonCondition {
+                operator: ComparisonOperator::IsDistinctFrom,
+                left: Expression::Identifier("active".to_string()),
+                right: Expression::Literal(Literal::Boolean(false)),
+            })
+        );
+        assert_eq!(
+            parse_where("SELECT * FROM users WHERE deleted_at IS NOT NULL"),
+            Condition::NullCheck(NullCheckCondition::IsNotNull {
+                identifier: "deleted_at".to_string()
+            })
+        );
+        assert_eq!(
+            parse_where("SELECT * FROM users WHERE deleted_at IS NULL"),
+            Condition::NullCheck(NullCheckCondition::IsNull {
+                identifier: "deleted_at".to_string()
+            })
+        );
+
+        assert!(parse("SELECT * FROM users WHERE active IS 1").is_err());
     }
 
     #[test]
-    fn test_delete_statement() {
-        let mut parser = Parser::new("DELETE FROM users WHERE age < 18".to_string());
-        let result = parser.parse();
-        println!("{:?}", result);
-        assert!(result.is_ok());
-        if let Ok(SQLStatement::Delete(delete_stmt)) = result {
-            assert_eq!(delete_stmt.table, "users");
-            assert!(delete_stmt.where_clause.is_some());
-        } else {
-            panic!("Expected Delete statement");
-        }
+    fn test_bare_identifier_is_boolean_shorthand() {
+        assert_eq!(
+            parse_where("SELECT * FROM users WHERE is_enabled AND x = 1"),
+            Condition::Logical(LogicalCondition {
+                operator: LogicalOperator::And,
+                left: Box::new(Condition::Comparison(ComparisonCondition {
+                    operator: ComparisonOperator::Equal,
+                    left: Expression::Identifier("is_enabled".to_string()),
+                    right: Expression::Literal(Literal::Boolean(true)),
+                })),
+                right: Box::new(Condition::Comparison(ComparisonCondition {
+                    operator: ComparisonOperator::Equal,
+                    left: Expression::Identifier("x".to_string()),
+                    right: Expression::Literal(Literal::Integer(1)),
+                })),
+            })
+        );
     }
 
     #[test]
-    fn test_create_table_statement() {
-        let mut parser = Parser::new("CREATE TABLE products (id INTEGER PRIMARY KEY, name VARCHAR(100) NOT NULL, price FLOAT)".to_string());
+    fn test_in_subquery() {
+        let mut parser = Parser::new(
+            "SELECT * FROM users WHERE id IN (SELECT user_id FROM orders WHERE total > 10) AND active"
+                .to_string(),
+        ).unwrap();
         let result = parser.parse();
-        assert!(
-            result.is_ok(),
-            "Failed to parse create table: {:?}",
-            result.unwrap_err()
+        assert!(parser.is_at_end());
+
+        let condition = match result {
+            Ok(SQLStatement::Select(SelectStatement {
+                where_clause: Some(where_clause),
+                ..
+            })) => where_clause.condition,
+            other => panic!("Expected Select with WHERE clause, got {:?}", other),
+        };
+        let Condition::Logical(LogicalCondition { left, .. }) = condition else {
+            panic!("Expected AND condition, got {:?}", condition);
+        };
+        let Condition::In(in_condition) = *left else {
+            panic!("Expected IN condition, got {:?}", left);
+        };
+        assert_eq!(in_condition.identifier, "id");
+        let InValues::Subquery(subquery) = in_condition.values else {
+            panic!("Expected IN subquery, got {:?}", in_condition.values);
+        };
+        assert_eq!(
+            subquery.columns,
+            vec![SelectColumn::Column("user_id".to_string())]
         );
-        if let Ok(SQLStatement::Create(create_stmt)) = result {
-            assert_eq!(create_stmt.table, "products");
-            assert_eq!(create_stmt.columns.len(), 3);
-        } else {
-            panic!("Expected Create statement");
+        assert_eq!(subquery.from, Some("orders".to_string()));
+        assert!(subquery.where_clause.is_some());
+
+        assert!(parse("SELECT * FROM users WHERE id IN (SELECT user_id FROM orders").is_err());
+    }
+
+    #[test]
+    fn test_in_list_requires_homogeneous_types() {
+        assert_eq!(
+            parse_where("SELECT * FROM users WHERE id IN (1, 2, 3)"),
+            Condition::In(InCondition {
+                identifier: "id".to_string(),
+                values: InValues::List(vec![
+                    Expression::Literal(Literal::Integer(1)),
+                    Expression::Literal(Literal::Integer(2)),
+                    Expression::Literal(Literal::Integer(3)),
+                ]),
+            })
+        );
+
+        for query in [
+            "SELECT * FROM users WHERE x IN (1, 'two')",
+            "SELECT * FROM users WHERE x IN ('one', 2)",
+            "SELECT * FROM users WHERE x IN (TRUE, 1)",
+        ] {
+            assert!(parse(query).is_err(), "Expected error for query: {}", query);
         }
     }
 
     #[test]
-    fn test_drop_table_statement() {
-        let mut parser = Parser::new("DROP TABLE old_users".to_string());
-        let result = parser.parse();
-        assert!(result.is_ok());
+    fn test_tokenize_to_string() {
+        let dump = tokenize_to_string("SELECT * FROM t").unwrap();
+        assert_eq!(
+            dump,
+            "Select(SELECT)@1:1\nStar(*)@1:8\nFrom(FROM)@1:10\nIdentifier(t)@1:15\nEof()@1:16"
+        );
+
+        // TRANSACTION is not reserved, so it scans as an identifier after BEGIN.
+        assert_eq!(
+            tokenize_to_string("BEGIN TRANSACTION").unwrap(),
+            "Begin(BEGIN)@1:1\nIdentifier(TRANSACTION)@1:7\nEof()@1:18"
+        );
+        assert_eq!(
+            tokenize_to_string("begin\n  transaction").unwrap(),
+            "Begin(begin)@1:1\nIdentifier(transaction)@2:3\nEof()@2:14"
+        );
+    }
+
+    #[test]
+    fn test_parse_expression_str() {
+        assert_eq!(
+            Parser::parse_expression_str("42"),
+            Ok(Expression::Literal(Literal::Integer(42)))
+        );
+        assert_eq!(
+            Parser::parse_expression_str("price"),
+            Ok(Expression::Identifier("price".to_string()))
+        );
+        assert_eq!(
+            Parser::parse_expression_str("price * (2 + tax) - 1"),
+            Ok(Expression::Arithmetic(ArithmeticExpression {
+                left: Box::new(Expression::Arithmetic(ArithmeticExpression {
+                    left: Box::new(Expression::Identifier("price".to_string())),
+                    operator: ArithmeticOperator::Multiply,
+                    right: Box::new(Expression::Arithmetic(ArithmeticExpression {
+                        left: Box::new(Expression::Literal(Literal::Integer(2))),
+                        operator: ArithmeticOperator::Add,
+                        right: Box::new(Expression::Identifier("tax".to_string())),
+                    })),
+                })),
+                operator: ArithmeticOperator::Subtract,
+                right: Box::new(Expression::Literal(Literal::Integer(1))),
+            }))
+        );
+        assert_eq!(
+            Parser::parse_expression_str("a / b + c"),
+            Ok(Expression::Arithmetic(ArithmeticExpression {
+                left: Box::new(Expression::Arithmetic(ArithmeticExpression {
+                    left: Box::new(Expression::Identifier("a".to_string())),
+                    operator: ArithmeticOperator::Divide,
+                    right: Box::new(Expression::Identifier("b".to_string())),
+                })),
+                operator: ArithmeticOperator::Add,
+                right: Box::new(Expression::Identifier("c".to_string())),
+            }))
+        );
+
+        assert_eq!(
+            Parser::parse_expression_str("-1"),
+            Ok(Expression::Literal(Literal::Integer(-1)))
+        );
+        assert_eq!(
+            Parser::parse_expression_str("-(a + 1)"),
+            Ok(Expression::Negate(Box::new(Expression::Arithmetic(
+                ArithmeticExpression {
+                    left: Box::new(Expression::Identifier("a".to_string())),
+                    operator: ArithmeticOperator::Add,
+                    right: Box::new(Expression::Literal(Literal::Integer(1))),
+                }
+            ))))
+        );
+        // Unary minus binds tighter than multiplication.
+        assert_eq!(
+            Parser::parse_expression_str("-a * 2.5"),
+            Ok(Expression::Arithmetic(ArithmeticExpression {
+                left: Box::new(Expression::Negate(Box::new(Expression::Identifier(
+                    "a".to_string()
+                )))),
+                operator: ArithmeticOperator::Multiply,
+                right: Box::new(Expression::Literal(Literal::Number(2.5))),
+            }))
+        );
+        assert_eq!(
+            Parser::parse_expression_str("-9223372036854775808"),
+            Ok(Expression::Literal(Literal::Integer(i64::MIN)))
+        );
+        assert_eq!(
+            Parser::parse_expression_str("- -1.5"),
+            Ok(Expression::Literal(Literal::Number(1.5)))
+        );
+        assert!(Parser::parse_expression_str("9223372036854775808").is_err());
+        assert!(Parser::parse_expression_str("-").is_err());
+
+        assert!(Parser::parse_expression_str("1 +").is_err());
+        assert!(Parser::parse_expression_str("(1 + 2").is_err());
+        assert!(Parser::parse_expression_str("1 2").is_err());
+    }
+
+    fn parse_order_by(sql: &str) -> Result<Vec<OrderByItem>, String> {
+        match parse(sql)? {
+            SQLStatement::Select(select_stmt) => Ok(select_stmt.order_by),
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_order_by() {
+        assert_eq!(
+            parse_order_by("SELECT name, price FROM products ORDER BY price DESC"),
+            Ok(vec![OrderByItem {
+                key: OrderKey::Expression(Expression::Identifier("price".to_string())),
+                descending: true,
+            }])
+        );
+        assert_eq!(
+            parse_order_by("SELECT name, price FROM products ORDER BY 1"),
+            Ok(vec![OrderByItem {
+                key: OrderKey::Position(1),
+                descending: false,
+            }])
+        );
+        assert_eq!(
+            parse_order_by("SELECT * FROM products WHERE price > 10 ORDER BY a ASC, b DESC"),
+            Ok(vec![
+                OrderByItem {
+                    key: OrderKey::Expression(Expression::Identifier("a".to_string())),
+                    descending: false,
+                },
+                OrderByItem {
+                    key: OrderKey::Expression(Expression::Identifier("b".to_string())),
+                    descending: true,
+                },
+            ])
+        );
+        assert_eq!(
+            parse_order_by("SELECT name FROM products ORDER BY price * 2"),
+            Ok(vec![OrderByItem {
+                key: OrderKey::Expression(Expression::Arithmetic(ArithmeticExpression {
+                    left: Box::new(Expression::Identifier("price".to_string())),
+                    operator: ArithmeticOperator::Multiply,
+                    right: Box::new(Expression::Literal(Literal::Integer(2))),
+                })),
+                descending: false,
+            }])
+        );
+
+        assert_eq!(
+            parse_order_by("SELECT name, price FROM products ORDER BY 1.0"),
+            Ok(vec![OrderByItem {
+                key: OrderKey::Expression(Expression::Literal(Literal::Number(1.0))),
+                descending: false,
+            }])
+        );
+        assert_eq!(
+            parse_order_by("SELECT name, price FROM products ORDER BY -1"),
+            Ok(vec![OrderByItem {
+                key: OrderKey::Expression(Expression::Literal(Literal::Integer(-1))),
+                descending: false,
+            }])
+        );
+
+        assert!(parse_order_by("SELECT name, price FROM products ORDER BY 3").is_err());
+        assert!(parse_order_by("SELECT name FROM products ORDER BY 0").is_err());
+        assert!(parse_order_by("SELECT name FROM products ORDER price").is_err());
+        assert!(parse_order_by("SELECT name FROM products ORDER BY").is_err());
+    }
+
+    #[test]
+    fn test_positional_parameters() {
+        let mut parser = Parser::new("SELECT * FROM t WHERE id = ?".to_string()).unwrap();
+        let result = parser.parse();
+        assert_eq!(parser.parameter_count(), 1);
+        let Ok(SQLStatement::Select(SelectStatement {
+            where_clause: Some(where_clause),
+            ..
+        })) = result
+        else {
+            panic!("Expected Select with WHERE clause, got {:?}", result);
+        };
+        assert_eq!(
+            where_clause.condition,
+            Condition::Comparison(ComparisonCondition {
+                operator: ComparisonOperator::Equal,
+                left: Expression::Identifier("id".to_string()),
+                right: Expression::Parameter(0),
+            })
+        );
+
+        let mut parser = Parser::new("INSERT INTO t (id, name) VALUES (?, ?)".to_string()).unwrap();
+        let result = parser.parse();
+        assert_eq!(parser.parameter_count(), 2);
+        if let Ok(SQLStatement::Insert(insert_stmt)) = result {
+            assert_eq!(
+                insert_stmt.values,
+                vec![vec![Expression::Parameter(0), Expression::Parameter(1)]]
+            );
+        } else {
+            panic!("Expected Insert statement, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parameters_in_in_lists() {
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE a IN (?, 2, ?)"),
+            Condition::In(InCondition {
+                identifier: "a".to_string(),
+                values: InValues::List(vec![
+                    Expression::Parameter(0),
+                    Expression::Literal(Literal::Integer(2)),
+                    Expression::Parameter(1),
+                ]),
+            })
+        );
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE (a, b) IN ((?, 'x'), (1, ?))"),
+            Condition::TupleIn(TupleInCondition {
+                columns: vec!["a".to_string(), "b".to_string()],
+                rows: vec![
+                    vec![
+                        Expression::Parameter(0),
+                        Expression::Literal(Literal::String("x".to_string())),
+                    ],
+                    vec![
+                        Expression::Literal(Literal::Integer(1)),
+                        Expression::Parameter(1),
+                    ],
+                ],
+            })
+        );
+        assert_eq!(
+            format_sql("select * from t where a in (?)").unwrap(),
+            "SELECT *\nFROM t\nWHERE a IN (?)"
+        );
+
+        // Literals are still type checked around the parameters.
+        for query in [
+            "SELECT * FROM t WHERE a IN (?, 1, 'x')",
+            "SELECT * FROM t WHERE (a, b) IN ((?, 'x'), (1, ?), (2, 3))",
+            "SELECT * FROM t WHERE a IN (b)",
+        ] {
+            assert!(parse(query).is_err(), "Expected error for query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_comments_are_ignored() {
+        let result = parse("-- leading comment\nSELECT name /* inline */ FROM users -- trailing");
+        if let Ok(SQLStatement::Select(select_stmt)) = result {
+            assert_eq!(
+                select_stmt.columns,
+                vec![SelectColumn::Column("name".to_string())]
+            );
+            assert_eq!(select_stmt.from, Some("users".to_string()));
+        } else {
+            panic!("Expected Select statement, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_format_sql() {
+        let messy = "select name,age   from users\n  where not (age < 18 or name is null)\n and (city in ('A\\'s', 'B') or score >= (bonus+1)*2)   order by age desc,   2;";
+        let formatted = format_sql(messy).unwrap();
+        assert_eq!(
+            formatted,
+            "SELECT name, age\nFROM users\nWHERE NOT (age < 18 OR name IS NULL) AND (city IN ('A\\'s', 'B') OR score >= (bonus + 1) * 2)\nORDER BY age DESC, 2"
+        );
+        assert_eq!(format_sql(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_format_sql_round_trips_every_statement() {
+        let queries = [
+            "SELECT * FROM t WHERE a = b - (c - d) AND e = f / (g * h) - 1 AND x IN (SELECT id FROM u WHERE y > 1)",
+            "SELECT * FROM t WHERE a = 1 OR (b = 2 OR c = 3)",
+            "EXPLAIN SELECT id FROM t WHERE id = ?",
+            "insert into t (a, b) values (1, 'x'), (2, 'line\\nbreak')",
+            "update t set a = a + 1, b = TRUE where c is not null",
+            "delete from t where flag",
+            "create table t (id integer not null, name varchar(20) not null unique, owner integer references users (id) on delete cascade, primary key (id, name), foreign key (owner) references users (id) on update set null)",
+            "drop table t",
+            "select distinct a from t order by a desc limit 5",
+            "select -a, -(a + 1), - -1, a - -1, -(-9223372036854775808) from t where a = -1 order by -1",
+            "insert into t values (-5, -2.5)",
+            "SELECT 10000000000000000000.5, 2.0, 0.25, 9223372036854775807 FROM t WHERE a > 123456789012345678",
+        ];
+
+        for query in queries {
+            let original = parse(query).unwrap();
+            let formatted = format_sql(query).unwrap();
+            let reparsed = parse(&formatted).unwrap();
+            assert_eq!(original, reparsed, "AST changed for:\n{}", formatted);
+            assert_eq!(format_sql(&formatted).unwrap(), formatted);
+        }
+    }
+
+    fn parse_select_columns(sql: &str) -> Vec<SelectColumn> {
+        match parse(sql) {
+            Ok(SQLStatement::Select(select_stmt)) => select_stmt.columns,
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_star_is_all_columns_or_multiplication() {
+        assert_eq!(
+            parse_select_columns("SELECT * FROM t"),
+            vec![SelectColumn::All]
+        );
+        assert_eq!(
+            parse_select_columns("SELECT a * b FROM t"),
+            vec![SelectColumn::Expression(Expression::Arithmetic(
+                ArithmeticExpression {
+                    left: Box::new(Expression::Identifier("a".to_string())),
+                    operator: ArithmeticOperator::Multiply,
+                    right: Box::new(Expression::Identifier("b".to_string())),
+                }
+            ))]
+        );
+        assert_eq!(
+            parse_select_columns("SELECT a, b * c FROM t"),
+            vec![
+                SelectColumn::Column("a".to_string()),
+                SelectColumn::Expression(Expression::Arithmetic(ArithmeticExpression {
+                    left: Box::new(Expression::Identifier("b".to_string())),
+                    operator: ArithmeticOperator::Multiply,
+                    right: Box::new(Expression::Identifier("c".to_string())),
+                })),
+            ]
+        );
+        assert_eq!(
+            parse_select_columns("SELECT a, * FROM t"),
+            vec![SelectColumn::Column("a".to_string()), SelectColumn::All]
+        );
+
+        for query in [
+            "SELECT a * FROM t",
+            "SELECT * * FROM t",
+            "SELECT * b FROM t",
+        ] {
+            let result = parse(query);
+            assert!(result.is_err(), "Expected error for query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_aggregate_select_columns() {
+        assert_eq!(
+            parse_select_columns(
+                "SELECT COUNT(*), count(id), SUM(price * 2), AVG(a), MIN(a), MAX(a) FROM t"
+            ),
+            vec![
+                SelectColumn::Aggregate(AggregateCall {
+                    function: AggregateFunction::Count,
+                    argument: None,
+                }),
+                SelectColumn::Aggregate(AggregateCall {
+                    function: AggregateFunction::Count,
+                    argument: Some(Expression::Identifier("id".to_string())),
+                }),
+                SelectColumn::Aggregate(AggregateCall {
+                    function: AggregateFunction::Sum,
+                    argument: Some(Expression::Arithmetic(ArithmeticExpression {
+                        left: Box::new(Expression::Identifier("price".to_string())),
+                        operator: ArithmeticOperator::Multiply,
+                        right: Box::new(Expression::Literal(Literal::Integer(2))),
+                    })),
+                }),
+                SelectColumn::Aggregate(AggregateCall {
+                    function: AggregateFunction::Avg,
+                    argument: Some(Expression::Identifier("a".to_string())),
+                }),
+                SelectColumn::Aggregate(AggregateCall {
+                    function: AggregateFunction::Min,
+                    argument: Some(Expression::Identifier("a".to_string())),
+                }),
+                SelectColumn::Aggregate(AggregateCall {
+                    function: AggregateFunction::Max,
+                    argument: Some(Expression::Identifier("a".to_string())),
+                }),
+            ]
+        );
+        // Without a following parenthesis the name is an ordinary column.
+        assert_eq!(
+            parse_select_columns("SELECT count FROM t"),
+            vec![SelectColumn::Column("count".to_string())]
+        );
+        assert_eq!(
+            format_sql("select count(*), sum(a) from t"),
+            Ok("SELECT COUNT(*), SUM(a)\nFROM t".to_string())
+        );
+
+        for query in [
+            "SELECT SUM(*) FROM t",
+            "SELECT COUNT() FROM t",
+            "SELECT COUNT(a, b) FROM t",
+            "DELETE FROM t RETURNING COUNT(*)",
+        ] {
+            let result = parse(query);
+            assert!(result.is_err(), "Expected error for query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_not_equal_operator() {
+        let expected = Condition::Comparison(ComparisonCondition {
+            operator: ComparisonOperator::NotEqual,
+            left: Expression::Identifier("a".to_string()),
+            right: Expression::Literal(Literal::Integer(1)),
+        });
+        assert_eq!(parse_where("SELECT * FROM t WHERE a != 1"), expected);
+        assert_eq!(parse_where("SELECT * FROM t WHERE a <> 1"), expected);
+    }
+
+    #[test]
+    fn test_match_any_and_expect_one_of() {
+        let mut parser = Parser::new("ASC DESC name".to_string()).unwrap();
+        let sort = [SQLTokenTypes::Asc, SQLTokenTypes::Desc];
+
+        assert_eq!(
+            parser
+                .match_any(&sort)
+                .map(|token| token.lexeme.to_string()),
+            Some("ASC".to_string())
+        );
+        assert_eq!(
+            parser
+                .expect_one_of(&sort, "Expect ASC or DESC")
+                .map(|token| token.lexeme.to_string()),
+            Ok("DESC".to_string())
+        );
+        assert!(parser.match_any(&sort).is_none());
+        assert_eq!(
+            parser
+                .expect_one_of(&sort, "Expect ASC or DESC")
+                .map(|token| token.lexeme.to_string()),
+            Err("Expect ASC or DESC".to_string())
+        );
+        // A failed match leaves the current token in place.
+        assert_eq!(&*parser.peek().lexeme, "name");
+        assert!(parser.match_any(&[SQLTokenTypes::Identifier]).is_some());
+        assert!(parser.match_any(&[SQLTokenTypes::Eof]).is_none());
+    }
+
+    #[test]
+    fn test_max_nesting_depth() {
+        let depth = 1_000;
+        let nested_condition = format!(
+            "SELECT a FROM t WHERE {}a = 1{}",
+            "(".repeat(depth),
+            ")".repeat(depth)
+        );
+        assert_eq!(
+            parse(&nested_condition),
+            Err(format!(
+                "max nesting depth exceeded ({} levels)",
+                DEFAULT_MAX_DEPTH
+            ))
+        );
+
+        let nested_not = format!("SELECT a FROM t WHERE {}a = 1", "NOT ".repeat(depth));
+        assert!(parse(&nested_not)
+            .unwrap_err()
+            .contains("max nesting depth"));
+
+        let nested_expression = format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(Parser::parse_expression_str(&nested_expression)
+            .unwrap_err()
+            .contains("max nesting depth"));
+
+        // The limit is configurable and nesting within it still parses.
+        let sql = "SELECT a FROM t WHERE ((a = 1))".to_string();
+        assert!(parse(&sql).is_ok());
+        assert!(Parser::new(sql.clone())
+            .map(|parser| parser.with_max_depth(2))
+            .and_then(|mut parser| parser.parse())
+            .is_ok());
+        assert!(Parser::new(sql)
+            .map(|parser| parser.with_max_depth(1))
+            .and_then(|mut parser| parser.parse())
+            .is_err());
+    }
+
+    #[test]
+    fn test_negative_literals() {
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE a = -1"),
+            Condition::Comparison(ComparisonCondition {
+                operator: ComparisonOperator::Equal,
+                left: Expression::Identifier("a".to_string()),
+                right: Expression::Literal(Literal::Integer(-1)),
+            })
+        );
+        match parse("INSERT INTO t VALUES (-5, 1 - -2)") {
+            Ok(SQLStatement::Insert(insert)) => assert_eq!(
+                insert.values,
+                vec![vec![
+                    Expression::Literal(Literal::Integer(-5)),
+                    Expression::Arithmetic(ArithmeticExpression {
+                        left: Box::new(Expression::Literal(Literal::Integer(1))),
+                        operator: ArithmeticOperator::Subtract,
+                        right: Box::new(Expression::Literal(Literal::Integer(-2))),
+                    }),
+                ]]
+            ),
+            other => panic!("Expected Insert statement, got {:?}", other),
+        }
+        assert_eq!(
+            format_sql("select -(a + 1) from t").unwrap(),
+            "SELECT -(a + 1)\nFROM t"
+        );
+    }
+
+    #[test]
+    fn test_between_condition() {
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE age BETWEEN 10 AND 20"),
+            Condition::Between(BetweenCondition {
+                identifier: "age".to_string(),
+                low: Expression::Literal(Literal::Integer(10)),
+                high: Expression::Literal(Literal::Integer(20)),
+                negated: false,
+            })
+        );
+        // The AND inside BETWEEN binds before the logical AND that follows it.
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE age NOT BETWEEN 10 AND 20 AND active"),
+            Condition::Logical(LogicalCondition {
+                operator: LogicalOperator::And,
+                left: Box::new(Condition::Between(BetweenCondition {
+                    identifier: "age".to_string(),
+                    low: Expression::Literal(Literal::Integer(10)),
+                    high: Expression::Literal(Literal::Integer(20)),
+                    negated: true,
+                })),
+                right: Box::new(Condition::Comparison(ComparisonCondition {
+                    operator: ComparisonOperator::Equal,
+                    left: Expression::Identifier("active".to_string()),
+                    right: Expression::Literal(Literal::Boolean(true)),
+                })),
+            })
+        );
+        assert_eq!(
+            format_sql("select * from t where age not between 10 and 20 + 1").unwrap(),
+            "SELECT *\nFROM t\nWHERE age NOT BETWEEN 10 AND 20 + 1"
+        );
+
+        assert!(parse("SELECT * FROM t WHERE age BETWEEN 10").is_err());
+        assert!(parse("SELECT * FROM t WHERE age BETWEEN 10 OR 20").is_err());
+    }
+
+    #[test]
+    fn test_like_condition() {
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE name LIKE 'A%'"),
+            Condition::Like(LikeCondition {
+                identifier: "name".to_string(),
+                pattern: "A%".to_string(),
+                escape: None,
+                negated: false,
+            })
+        );
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE discount NOT LIKE '10!%' ESCAPE '!'"),
+            Condition::Like(LikeCondition {
+                identifier: "discount".to_string(),
+                pattern: "10!%".to_string(),
+                escape: Some('!'),
+                negated: true,
+            })
+        );
+        assert_eq!(
+            format_sql("select * from t where code like 'a!_b%' escape '!' and name not like '%z'")
+                .unwrap(),
+            "SELECT *\nFROM t\nWHERE code LIKE 'a!_b%' ESCAPE '!' AND name NOT LIKE '%z'"
+        );
+
+        // A backslash escape character is written '\\'; the pattern keeps '\%' as is.
+        assert_eq!(
+            parse_where(r"SELECT * FROM t WHERE path LIKE 'a\%' ESCAPE '\\'"),
+            Condition::Like(LikeCondition {
+                identifier: "path".to_string(),
+                pattern: r"a\%".to_string(),
+                escape: Some('\\'),
+                negated: false,
+            })
+        );
+        assert_eq!(
+            format_sql(r"select * from t where path like 'a\%' escape '\\'").unwrap(),
+            "SELECT *\nFROM t\nWHERE path LIKE 'a\\\\%' ESCAPE '\\\\'"
+        );
+        assert!(parse(r"SELECT * FROM t WHERE path LIKE 'a\%' ESCAPE '\'").is_err());
+        assert!(parse("SELECT * FROM t WHERE name LIKE 5").is_err());
+        assert!(parse("SELECT * FROM t WHERE name LIKE 'a' ESCAPE '!!'").is_err());
+        assert!(parse("SELECT * FROM t WHERE name LIKE 'a' ESCAPE ''").is_err());
+        assert!(parse("SELECT * FROM t WHERE name LIKE 'a!' ESCAPE '!'").is_err());
+        assert!(parse("SELECT * FROM t WHERE name LIKE").is_err());
+    }
+
+    #[test]
+    fn test_parse_with_recovery() {
+        let mut parser = Parser::new(
+            "SELECT * FROM;\nDELETE FROM users WHERE id = 1;\nUPDATE users name = 'x'; ;"
+                .to_string(),
+        )
+        .unwrap();
+        let (statements, errors) = parser.parse_with_recovery();
+
+        assert_eq!(statements.len(), 1);
+        assert!(matches!(statements[0], SQLStatement::Delete(_)));
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| (error.line, error.column))
+                .collect::<Vec<_>>(),
+            vec![(1, 1), (3, 1)]
+        );
+        assert_eq!(errors[0].message, "Expected table name after FROM");
+
+        // Two statements without a `;` between them are a single error.
+        let mut parser =
+            Parser::new("DROP TABLE a DROP TABLE b; DROP TABLE c".to_string()).unwrap();
+        let (statements, errors) = parser.parse_with_recovery();
+        assert_eq!(statements.len(), 1);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_is_distinct_from() {
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE a IS DISTINCT FROM b"),
+            Condition::Comparison(ComparisonCondition {
+                operator: ComparisonOperator::IsDistinctFrom,
+                left: Expression::Identifier("a".to_string()),
+                right: Expression::Identifier("b".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE a IS NOT DISTINCT FROM 'x'"),
+            Condition::Comparison(ComparisonCondition {
+                operator: ComparisonOperator::IsNotDistinctFrom,
+                left: Expression::Identifier("a".to_string()),
+                right: Expression::Literal(Literal::String("x".to_string())),
+            })
+        );
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE a IS NOT DISTINCT FROM NULL"),
+            Condition::NullCheck(NullCheckCondition::IsNull {
+                identifier: "a".to_string()
+            })
+        );
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE a IS DISTINCT FROM NULL"),
+            Condition::NullCheck(NullCheckCondition::IsNotNull {
+                identifier: "a".to_string()
+            })
+        );
+        assert_eq!(
+            format_sql("select * from t where a is not distinct from b + 1").unwrap(),
+            "SELECT *\nFROM t\nWHERE a IS NOT DISTINCT FROM b + 1"
+        );
+
+        assert!(parse("SELECT * FROM t WHERE a IS DISTINCT b").is_err());
+        assert!(parse("SELECT * FROM t WHERE a IS DISTINCT FROM").is_err());
+    }
+
+    #[test]
+    fn test_keyword_configured_as_identifier() {
+        let sql = "SELECT limit FROM t";
+        assert!(parse(sql).is_err());
+
+        let config = ScannerConfig::default().without_keyword("limit");
+        match Parser::new_with_config(sql.to_string(), config).and_then(|mut parser| parser.parse())
+        {
+            Ok(SQLStatement::Select(select_stmt)) => {
+                assert_eq!(
+                    select_stmt.columns,
+                    vec![SelectColumn::Column("limit".to_string())]
+                );
+            }
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_alter_table_rename() {
+        assert_eq!(
+            parse("ALTER TABLE users RENAME TO customers"),
+            Ok(SQLStatement::Alter(AlterStatement {
+                table: "users".to_string(),
+                action: AlterAction::RenameTable("customers".to_string()),
+            }))
+        );
+        assert_eq!(
+            parse("alter table users rename column name to full_name;"),
+            Ok(SQLStatement::Alter(AlterStatement {
+                table: "users".to_string(),
+                action: AlterAction::RenameColumn {
+                    from: "name".to_string(),
+                    to: "full_name".to_string(),
+                },
+            }))
+        );
+        // A column that is itself called "column".
+        assert_eq!(
+            parse("ALTER TABLE t RENAME column TO c"),
+            Ok(SQLStatement::Alter(AlterStatement {
+                table: "t".to_string(),
+                action: AlterAction::RenameColumn {
+                    from: "column".to_string(),
+                    to: "c".to_string(),
+                },
+            }))
+        );
+        assert_eq!(
+            format_sql("alter table users rename a to b").unwrap(),
+            "ALTER TABLE users RENAME COLUMN a TO b"
+        );
+
+        assert!(parse("ALTER TABLE users RENAME").is_err());
+        assert!(parse("ALTER TABLE users RENAME TO").is_err());
+        assert!(parse("ALTER TABLE users RENAME COLUMN a b").is_err());
+        assert!(parse("ALTER users RENAME TO x").is_err());
+    }
+
+    #[test]
+    fn test_select_distinct() {
+        match parse("SELECT DISTINCT a, b FROM t") {
+            Ok(SQLStatement::Select(select_stmt)) => {
+                assert!(select_stmt.distinct);
+                assert_eq!(
+                    select_stmt.columns,
+                    vec![
+                        SelectColumn::Column("a".to_string()),
+                        SelectColumn::Column("b".to_string())
+                    ]
+                );
+            }
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+        match parse("SELECT a FROM t") {
+            Ok(SQLStatement::Select(select_stmt)) => assert!(!select_stmt.distinct),
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+        assert_eq!(
+            format_sql("select distinct a from t").unwrap(),
+            "SELECT DISTINCT a\nFROM t"
+        );
+
+        assert!(parse("SELECT DISTINCT FROM t").is_err());
+    }
+
+    #[test]
+    fn test_select_limit() {
+        match parse("SELECT a FROM t WHERE a > 1 ORDER BY a LIMIT 10 FOR UPDATE") {
+            Ok(SQLStatement::Select(select_stmt)) => {
+                assert_eq!(select_stmt.limit, Some(10));
+                assert_eq!(select_stmt.order_by.len(), 1);
+                assert!(select_stmt.for_update);
+            }
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+        match parse("SELECT a FROM t") {
+            Ok(SQLStatement::Select(select_stmt)) => assert_eq!(select_stmt.limit, None),
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+        assert_eq!(
+            format_sql("select a from t limit 0").unwrap(),
+            "SELECT a\nFROM t\nLIMIT 0"
+        );
+
+        assert_eq!(
+            parse("SELECT a FROM t LIMIT 1.5"),
+            Err("LIMIT must be a non-negative integer, found 1.5".to_string())
+        );
+        for query in [
+            "SELECT a FROM t LIMIT",
+            "SELECT a FROM t LIMIT -1",
+            "SELECT a FROM t LIMIT 'x'",
+            "SELECT a FROM t LIMIT 1 ORDER BY a",
+        ] {
+            assert!(parse(query).is_err(), "Expected error for query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_select_for_update() {
+        match parse("SELECT id FROM accounts WHERE id = 1 ORDER BY id FOR UPDATE;") {
+            Ok(SQLStatement::Select(select_stmt)) => assert!(select_stmt.for_update),
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+        match parse("SELECT id FROM accounts") {
+            Ok(SQLStatement::Select(select_stmt)) => assert!(!select_stmt.for_update),
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+        assert_eq!(
+            format_sql("select * from accounts where id = 1 for update").unwrap(),
+            "SELECT *\nFROM accounts\nWHERE id = 1\nFOR UPDATE"
+        );
+
+        assert!(parse("SELECT id FROM accounts FOR").is_err());
+        assert!(parse("SELECT id FROM accounts FOR UPDATE WHERE id = 1").is_err());
+        assert!(parse("SELECT 1 FOR UPDATE").is_err());
+        assert!(parse("SELECT * FROM a WHERE id IN (SELECT id FROM b FOR UPDATE)").is_err());
+    }
+
+    #[test]
+    fn test_tokenize_then_parse() {
+        let tokens = tokenize("SELECT name FROM users WHERE id = 1").unwrap();
+        assert_eq!(tokens.last().unwrap().token_type, SQLTokenTypes::Eof);
+        match Parser::from_tokens(tokens).parse() {
+            Ok(SQLStatement::Select(select_stmt)) => {
+                assert_eq!(select_stmt.from, Some("users".to_string()));
+            }
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+
+        // Retained comments are dropped and a missing Eof is supplied.
+        let mut tokens = Scanner::new_with_comments("DROP TABLE t -- done".to_string(), true)
+            .scan_tokens()
+            .unwrap();
+        tokens.pop();
+        assert_eq!(tokens.last().unwrap().token_type, SQLTokenTypes::Comment);
+        assert!(Parser::from_tokens(tokens).parse().is_ok());
+        assert!(Parser::from_tokens(Vec::new()).parse().is_err());
+    }
+
+    #[test]
+    fn test_comment_on() {
+        assert_eq!(
+            parse("COMMENT ON COLUMN users.email IS 'Login address'"),
+            Ok(SQLStatement::Comment(CommentStatement {
+                target: CommentTarget::Column {
+                    table: "users".to_string(),
+                    column: "email".to_string(),
+                },
+                comment: Some("Login address".to_string()),
+            }))
+        );
+        assert_eq!(
+            parse("comment on table users is null;"),
+            Ok(SQLStatement::Comment(CommentStatement {
+                target: CommentTarget::Table("users".to_string()),
+                comment: None,
+            }))
+        );
+        assert_eq!(
+            format_sql("comment on table users is 'people who can log in'").unwrap(),
+            "COMMENT ON TABLE users IS 'people who can log in'"
+        );
+        // COMMENT is still usable as a column name.
+        assert_eq!(
+            parse_select_columns("SELECT comment FROM posts"),
+            vec![SelectColumn::Column("comment".to_string())]
+        );
+
+        assert!(parse("COMMENT ON COLUMN users IS 'x'").is_err());
+        assert!(parse("COMMENT ON INDEX idx IS 'x'").is_err());
+        assert!(parse("COMMENT ON TABLE users IS 5").is_err());
+        assert!(parse("COMMENT ON TABLE users").is_err());
+    }
+
+    #[test]
+    fn test_returning_clause() {
+        match parse("INSERT INTO users (name) VALUES ('John') RETURNING id, name") {
+            Ok(SQLStatement::Insert(insert)) => assert_eq!(
+                insert.returning,
+                Some(vec![
+                    SelectColumn::Column("id".to_string()),
+                    SelectColumn::Column("name".to_string()),
+                ])
+            ),
+            other => panic!("Expected Insert statement, got {:?}", other),
+        }
+        match parse("DELETE FROM users WHERE id = 1 RETURNING *;") {
+            Ok(SQLStatement::Delete(delete)) => {
+                assert_eq!(delete.returning, Some(vec![SelectColumn::All]))
+            }
+            other => panic!("Expected Delete statement, got {:?}", other),
+        }
+        match parse("UPDATE users SET visits = 1") {
+            Ok(SQLStatement::Update(update)) => assert_eq!(update.returning, None),
+            other => panic!("Expected Update statement, got {:?}", other),
+        }
+        assert_eq!(
+            format_sql("update users set visits = 1 where id = 2 returning visits + 1").unwrap(),
+            "UPDATE users\nSET visits = 1\nWHERE id = 2\nRETURNING visits + 1"
+        );
+
+        assert!(parse("DELETE FROM users RETURNING").is_err());
+        assert!(parse("DELETE FROM users RETURNING id WHERE id = 1").is_err());
+    }
+
+    #[test]
+    fn test_auto_increment_columns() {
+        let columns = |sql: &str| match parse(sql) {
+            Ok(SQLStatement::Create(create)) => create.columns,
+            other => panic!("Expected Create statement, got {:?}", other),
+        };
+
+        let serial = columns("CREATE TABLE t (id SERIAL PRIMARY KEY, name TEXT)");
+        assert_eq!(serial[0].data_type, DataType::Integer);
+        assert_eq!(
+            serial[0].constraints,
+            vec![
+                ColumnConstraint::AutoIncrement,
+                ColumnConstraint::PrimaryKey
+            ]
+        );
+        assert!(serial[1].constraints.is_empty());
+
+        let explicit = columns("CREATE TABLE t (id INTEGER PRIMARY KEY AUTOINCREMENT)");
+        assert_eq!(
+            explicit[0].constraints,
+            vec![
+                ColumnConstraint::PrimaryKey,
+                ColumnConstraint::AutoIncrement
+            ]
+        );
+        assert_eq!(
+            format_sql("create table t (id serial)").unwrap(),
+            "CREATE TABLE t (\n    id INTEGER AUTOINCREMENT\n)"
+        );
+
+        assert!(parse("CREATE TABLE t (name TEXT AUTOINCREMENT)").is_err());
+        assert!(parse("CREATE TABLE t (id SERIAL AUTOINCREMENT)").is_err());
+    }
+
+    #[test]
+    fn test_tuple_in_condition() {
+        assert_eq!(
+            parse_where("SELECT * FROM t WHERE (a, b) IN ((1, 'x'), (3, 'y'))"),
+            Condition::TupleIn(TupleInCondition {
+                columns: vec!["a".to_string(), "b".to_string()],
+                rows: vec![
+                    vec![
+                        Expression::Literal(Literal::Integer(1)),
+                        Expression::Literal(Literal::String("x".to_string())),
+                    ],
+                    vec![
+                        Expression::Literal(Literal::Integer(3)),
+                        Expression::Literal(Literal::String("y".to_string())),
+                    ],
+                ],
+            })
+        );
+        // Grouped conditions still parse as before.
+        assert!(matches!(
+            parse_where("SELECT * FROM t WHERE (a = 1) AND (a, b) IN ((1, 2))"),
+            Condition::Logical(_)
+        ));
+        assert_eq!(
+            format_sql("select * from t where not (a,b) in ((1,2),(3,4))").unwrap(),
+            "SELECT *\nFROM t\nWHERE NOT ((a, b) IN ((1, 2), (3, 4)))"
+        );
+
+        assert!(parse("SELECT * FROM t WHERE (a, b) IN ((1, 2, 3))").is_err());
+        assert!(parse("SELECT * FROM t WHERE (a, b) IN ((1, 2), (3, 'x'))").is_err());
+        assert!(parse("SELECT * FROM t WHERE (a, b) IN (1, 2)").is_err());
+        assert!(parse("SELECT * FROM t WHERE (a, b) = (1, 2)").is_err());
+    }
+
+    #[test]
+    fn test_integer_literals_are_exact() {
+        assert_eq!(
+            parse_select_columns("SELECT 9223372036854775807"),
+            vec![SelectColumn::Expression(Expression::Literal(
+                Literal::Integer(i64::MAX)
+            ))]
+        );
+        assert_eq!(
+            format_sql("SELECT a FROM t WHERE a = 123456789012345678").unwrap(),
+            "SELECT a\nFROM t\nWHERE a = 123456789012345678"
+        );
+        // Integers and decimals may be mixed in an IN list.
+        assert!(matches!(
+            parse_where("SELECT * FROM t WHERE a IN (1, 2.5)"),
+            Condition::In(_)
+        ));
+    }
+
+    #[test]
+    fn test_select_without_from() {
+        match parse("SELECT 1 + 2") {
+            Ok(SQLStatement::Select(select_stmt)) => {
+                assert_eq!(select_stmt.from, None);
+                assert_eq!(
+                    select_stmt.columns,
+                    vec![SelectColumn::Expression(Expression::Arithmetic(
+                        ArithmeticExpression {
+                            left: Box::new(Expression::Literal(Literal::Integer(1))),
+                            operator: ArithmeticOperator::Add,
+                            right: Box::new(Expression::Literal(Literal::Integer(2))),
+                        }
+                    ))]
+                );
+            }
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+        assert_eq!(
+            parse_select_columns("SELECT 'x';"),
+            vec![SelectColumn::Expression(Expression::Literal(
+                Literal::String("x".to_string())
+            ))]
+        );
+        assert_eq!(format_sql("select 1+1").unwrap(), "SELECT 1 + 1");
+
+        // ORDER BY still applies without FROM; WHERE and FOR UPDATE need a table.
+        match parse("SELECT 1, 2 ORDER BY 2 DESC") {
+            Ok(SQLStatement::Select(select_stmt)) => assert_eq!(
+                select_stmt.order_by,
+                vec![OrderByItem {
+                    key: OrderKey::Position(2),
+                    descending: true,
+                }]
+            ),
+            other => panic!("Expected Select statement, got {:?}", other),
+        }
+        assert_eq!(
+            format_sql("select 1 order by 1").unwrap(),
+            "SELECT 1\nORDER BY 1"
+        );
+        assert!(parse("SELECT 1 ORDER BY 2").is_err());
+        assert_eq!(
+            parse("SELECT 1 WHERE a = 1"),
+            Err("WHERE requires a FROM clause".to_string())
+        );
+        assert_eq!(
+            parse("SELECT 1 FOR UPDATE"),
+            Err("FOR UPDATE requires a FROM clause".to_string())
+        );
+        assert!(parse("SELECT *").is_err());
+    }
+
+    #[test]
+    fn test_string_functions_and_concatenation() {
+        assert_eq!(
+            parse_select_columns("SELECT UPPER(name) || '!' FROM t"),
+            vec![SelectColumn::Expression(Expression::Concat(
+                ConcatExpression {
+                    left: Box::new(Expression::Function(FunctionCall {
+                        function: ScalarFunction::Upper,
+                        arguments: vec![Expression::Identifier("name".to_string())],
+                    })),
+                    right: Box::new(Expression::Literal(Literal::String("!".to_string()))),
+                }
+            ))]
+        );
+        assert_eq!(
+            Parser::parse_expression_str("a || b + 1 || c"),
+            Ok(Expression::Concat(ConcatExpression {
+                left: Box::new(Expression::Concat(ConcatExpression {
+                    left: Box::new(Expression::Identifier("a".to_string())),
+                    right: Box::new(Expression::Arithmetic(ArithmeticExpression {
+                        left: Box::new(Expression::Identifier("b".to_string())),
+                        operator: ArithmeticOperator::Add,
+                        right: Box::new(Expression::Literal(Literal::Integer(1))),
+                    })),
+                })),
+                right: Box::new(Expression::Identifier("c".to_string())),
+            }))
+        );
+        assert_eq!(
+            Parser::parse_expression_str("substr(lower(name), 1, length(name) - 1)"),
+            Ok(Expression::Function(FunctionCall {
+                function: ScalarFunction::Substr,
+                arguments: vec![
+                    Expression::Function(FunctionCall {
+                        function: ScalarFunction::Lower,
+                        arguments: vec![Expression::Identifier("name".to_string())],
+                    }),
+                    Expression::Literal(Literal::Integer(1)),
+                    Expression::Arithmetic(ArithmeticExpression {
+                        left: Box::new(Expression::Function(FunctionCall {
+                            function: ScalarFunction::Length,
+                            arguments: vec![Expression::Identifier("name".to_string())],
+                        })),
+                        operator: ArithmeticOperator::Subtract,
+                        right: Box::new(Expression::Literal(Literal::Integer(1))),
+                    }),
+                ],
+            }))
+        );
+        assert_eq!(
+            format_sql("select upper(first) || ' ' || lower(last) from people").unwrap(),
+            "SELECT UPPER(first) || ' ' || LOWER(last)\nFROM people"
+        );
+
+        assert!(Parser::parse_expression_str("UPPER()").is_err());
+        assert!(Parser::parse_expression_str("UPPER(a, b)").is_err());
+        assert!(Parser::parse_expression_str("SUBSTR(a)").is_err());
+        assert!(Parser::parse_expression_str("REVERSE(a)").is_err());
+        assert!(Parser::parse_expression_str("a ||").is_err());
+    }
+
+    #[test]
+    fn test_insert_statement() {
+        let result = parse("INSERT INTO users (name, age) VALUES ('John Doe', 30)");
+        assert!(result.is_ok());
+        if let Ok(SQLStatement::Insert(insert_stmt)) = result {
+            assert_eq!(insert_stmt.table, "users");
+            assert_eq!(insert_stmt.columns, vec!["name", "age"]);
+            assert_eq!(insert_stmt.values.len(), 1);
+            assert_eq!(insert_stmt.values[0].len(), 2);
+            assert_eq!(
+                insert_stmt.values[0][0],
+                Expression::Literal(Literal::String("John Doe".to_string()))
+            );
+        } else {
+            panic!("Expected Insert statement");
+        }
+    }
+
+    #[test]
+    fn test_escaped_string_literal() {
+        assert_eq!(
+            parse_where(r"SELECT * FROM users WHERE name = 'O\'Brien'"),
+            Condition::Comparison(ComparisonCondition {
+                operator: ComparisonOperator::Equal,
+                left: Expression::Identifier("name".to_string()),
+                right: Expression::Literal(Literal::String("O'Brien".to_string())),
+            })
+        );
+    }
+
+    #[test]
+    fn test_multi_row_insert() {
+        let result =
+            parse("INSERT INTO users (name, age) VALUES ('John', 30), ('Jane', 25), ('Max', 41)");
+        if let Ok(SQLStatement::Insert(insert_stmt)) = result {
+            assert_eq!(insert_stmt.values.len(), 3);
+            assert_eq!(
+                insert_stmt.values[2],
+                vec![
+                    Expression::Literal(Literal::String("Max".to_string())),
+                    Expression::Literal(Literal::Integer(41)),
+                ]
+            );
+        } else {
+            panic!("Expected Insert statement, got {:?}", result);
+        }
+
+        let result =
+            parse("INSERT INTO users (name, age) VALUES ('John', 30), ('Jane', 25, 'extra')");
+        assert_eq!(
+            result.unwrap_err(),
+            "VALUES tuple 2 has 3 values, expected 2"
+        );
+
+        let result = parse("INSERT INTO users VALUES (1, 2), (3)");
+        assert_eq!(
+            result.unwrap_err(),
+            "VALUES tuple 2 has 1 values, expected 2"
+        );
+
+        let result = parse("INSERT INTO users (name, age) VALUES ('John')");
+        assert_eq!(
+            result.unwrap_err(),
+            "VALUES tuple 1 has 1 values, expected 2"
+        );
+    }
+
+    #[test]
+    fn test_update_statement() {
+        let result = parse("UPDATE users SET age = 31 WHERE name = 'John Doe'");
+        assert!(result.is_ok());
+        if let Ok(SQLStatement::Update(update_stmt)) = result {
+            assert_eq!(update_stmt.table, "users");
+            assert_eq!(update_stmt.assignments.len(), 1);
+            assert!(update_stmt.where_clause.is_some());
+        } else {
+            panic!("Expected Update statement");
+        }
+    }
+
+    #[test]
+    fn test_delete_statement() {
+        let result = parse("DELETE FROM users WHERE age < 18");
+        println!("{:?}", result);
+        assert!(result.is_ok());
+        if let Ok(SQLStatement::Delete(delete_stmt)) = result {
+            assert_eq!(delete_stmt.table, "users");
+            assert!(delete_stmt.where_clause.is_some());
+        } else {
+            panic!("Expected Delete statement");
+        }
+    }
+
+    #[test]
+    fn test_create_table_statement() {
+        let result = parse("CREATE TABLE products (id INTEGER PRIMARY KEY, name VARCHAR(100) NOT NULL, price FLOAT)");
+        assert!(
+            result.is_ok(),
+            "Failed to parse create table: {:?}",
+            result.unwrap_err()
+        );
+        if let Ok(SQLStatement::Create(create_stmt)) = result {
+            assert_eq!(create_stmt.table, "products");
+            assert_eq!(create_stmt.columns.len(), 3);
+            assert_eq!(create_stmt.columns[0].data_type, DataType::Integer);
+            assert_eq!(
+                create_stmt.columns[1].data_type,
+                DataType::Varchar(Some(100))
+            );
+            assert_eq!(create_stmt.columns[2].data_type, DataType::Float);
+        } else {
+            panic!("Expected Create statement");
+        }
+    }
+
+    #[test]
+    fn test_create_table_column_constraints() {
+        let result =
+            parse("CREATE TABLE t (id INTEGER PRIMARY KEY, email VARCHAR UNIQUE NOT NULL)");
+        if let Ok(SQLStatement::Create(create_stmt)) = result {
+            assert_eq!(
+                create_stmt.columns[0].constraints,
+                vec![ColumnConstraint::PrimaryKey]
+            );
+            assert_eq!(
+                create_stmt.columns[1].constraints,
+                vec![ColumnConstraint::Unique, ColumnConstraint::NotNull]
+            );
+        } else {
+            panic!("Expected Create statement, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_create_table_composite_primary_key() {
+        let result = parse("CREATE TABLE enrollments (student_id INTEGER, course_id INTEGER, seat INTEGER, PRIMARY KEY (student_id, course_id), UNIQUE (course_id, seat))");
+        if let Ok(SQLStatement::Create(create_stmt)) = result {
+            assert_eq!(create_stmt.columns.len(), 3);
+            assert_eq!(
+                create_stmt.table_constraints,
+                vec![
+                    TableConstraint::PrimaryKey(vec![
+                        "student_id".to_string(),
+                        "course_id".to_string()
+                    ]),
+                    TableConstraint::Unique(vec!["course_id".to_string(), "seat".to_string()]),
+                ]
+            );
+        } else {
+            panic!("Expected Create statement, got {:?}", result);
+        }
+
+        assert!(parse("CREATE TABLE t (PRIMARY KEY (a))").is_err());
+    }
+
+    #[test]
+    fn test_create_table_constraints_are_validated() {
+        for query in [
+            "CREATE TABLE t (a INTEGER, PRIMARY KEY (a), PRIMARY KEY (a))",
+            "CREATE TABLE t (a INTEGER PRIMARY KEY, b INTEGER, PRIMARY KEY (b))",
+            "CREATE TABLE t (a INTEGER PRIMARY KEY, b INTEGER PRIMARY KEY)",
+        ] {
+            assert_eq!(
+                parse(query),
+                Err("Table t has more than one PRIMARY KEY".to_string()),
+                "{}",
+                query
+            );
+        }
+
+        assert_eq!(
+            parse("CREATE TABLE t (a INTEGER, PRIMARY KEY (a, b))"),
+            Err("PRIMARY KEY references unknown column b".to_string())
+        );
+        assert_eq!(
+            parse("CREATE TABLE t (a INTEGER, UNIQUE (A))"),
+            Err("UNIQUE references unknown column A".to_string())
+        );
+        assert_eq!(
+            parse("CREATE TABLE t (a INTEGER, FOREIGN KEY (b) REFERENCES u (id))"),
+            Err("FOREIGN KEY references unknown column b".to_string())
+        );
+
+        // Constraints declared after the columns they name are fine.
+        assert!(parse("CREATE TABLE t (a INTEGER, b INTEGER PRIMARY KEY, UNIQUE (a, b))").is_ok());
+    }
+
+    #[test]
+    fn test_create_table_foreign_key_actions() {
+        let result = parse("CREATE TABLE orders (id INTEGER, user_id INTEGER REFERENCES users (id) ON DELETE CASCADE, coupon_id INTEGER, FOREIGN KEY (coupon_id) REFERENCES coupons (id) ON UPDATE RESTRICT ON DELETE SET NULL)");
+        let Ok(SQLStatement::Create(create_stmt)) = result else {
+            panic!("Expected Create statement, got {:?}", result);
+        };
+
+        assert_eq!(
+            create_stmt.columns[1].constraints,
+            vec![ColumnConstraint::References(ForeignKeyReference {
+                table: "users".to_string(),
+                columns: vec!["id".to_string()],
+                on_delete: Some(ReferentialAction::Cascade),
+                on_update: None,
+            })]
+        );
+        assert_eq!(
+            create_stmt.table_constraints,
+            vec![TableConstraint::ForeignKey {
+                columns: vec!["coupon_id".to_string()],
+                references: ForeignKeyReference {
+                    table: "coupons".to_string(),
+                    columns: vec!["id".to_string()],
+                    on_delete: Some(ReferentialAction::SetNull),
+                    on_update: Some(ReferentialAction::Restrict),
+                },
+            }]
+        );
+
+        for query in [
+            "CREATE TABLE t (a INTEGER REFERENCES u (id) ON DELETE)",
+            "CREATE TABLE t (a INTEGER REFERENCES u (id) ON DELETE CASCADE ON DELETE RESTRICT)",
+            "CREATE TABLE t (a INTEGER REFERENCES u (id) ON INSERT CASCADE)",
+            "CREATE TABLE t (a INTEGER REFERENCES u)",
+        ] {
+            let result = parse(query);
+            assert!(result.is_err(), "Expected error for query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_create_table_character_types() {
+        let result = parse("CREATE TABLE t (name VARCHAR(50), code CHAR(2), flag CHAR, bio TEXT)");
+        if let Ok(SQLStatement::Create(create_stmt)) = result {
+            let types: Vec<&DataType> = create_stmt.columns.iter().map(|c| &c.data_type).collect();
+            assert_eq!(
+                types,
+                vec![
+                    &DataType::Varchar(Some(50)),
+                    &DataType::Char(Some(2)),
+                    &DataType::Char(None),
+                    &DataType::Text,
+                ]
+            );
+        } else {
+            panic!("Expected Create statement, got {:?}", result);
+        }
+
+        for query in [
+            "CREATE TABLE t (name VARCHAR(0))",
+            "CREATE TABLE t (name VARCHAR(2.5))",
+            "CREATE TABLE t (name CHAR())",
+        ] {
+            assert!(parse(query).is_err(), "Expected error for query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_identifiers_preserve_case() {
+        let result = parse("create table Accounts (userName text, IsActive boolean)");
+        if let Ok(SQLStatement::Create(create_stmt)) = result {
+            assert_eq!(create_stmt.table, "Accounts");
+            assert_eq!(create_stmt.columns[0].name, "userName");
+            assert_eq!(create_stmt.columns[0].data_type, DataType::Text);
+            assert_eq!(create_stmt.columns[1].name, "IsActive");
+        } else {
+            panic!("Expected Create statement, got {:?}", result);
+        }
+
+        let result = parse("select USERNAME, username from accounts");
+        if let Ok(SQLStatement::Select(select_stmt)) = result {
+            assert_eq!(
+                select_stmt.columns,
+                vec![
+                    SelectColumn::Column("USERNAME".to_string()),
+                    SelectColumn::Column("username".to_string()),
+                ]
+            );
+        } else {
+            panic!("Expected Select statement, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_drop_table_statement() {
+        let result = parse("DROP TABLE old_users");
+        assert!(result.is_ok());
         if let Ok(SQLStatement::Drop(drop_stmt)) = result {
             assert_eq!(drop_stmt.table, "old_users");
         } else {
@@ -592,6 +3149,98 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_vacuum_statement() {
+        assert_eq!(
+            parse("VACUUM old_users;"),
+            Ok(SQLStatement::Vacuum(VacuumStatement {
+                table: "old_users".to_string()
+            }))
+        );
+        assert_eq!(
+            format_sql("vacuum old_users"),
+            Ok("VACUUM old_users".to_string())
+        );
+
+        for query in ["VACUUM", "VACUUM TABLE t", "VACUUM a b"] {
+            assert!(parse(query).is_err(), "Expected error for query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_explain_statement() {
+        let result = parse("EXPLAIN SELECT * FROM t WHERE pk = 1");
+        if let Ok(SQLStatement::Explain(select_stmt)) = result {
+            assert_eq!(select_stmt.columns, vec![SelectColumn::All]);
+            assert_eq!(select_stmt.from, Some("t".to_string()));
+            assert!(select_stmt.where_clause.is_some());
+        } else {
+            panic!("Expected Explain statement, got {:?}", result);
+        }
+
+        let result = parse("EXPLAIN DROP TABLE t");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_transaction_statements() {
+        let cases = [
+            ("BEGIN", TransactionStatement::Begin),
+            ("begin transaction;", TransactionStatement::Begin),
+            ("COMMIT", TransactionStatement::Commit),
+            ("ROLLBACK", TransactionStatement::Rollback),
+        ];
+        for (query, expected) in cases {
+            assert_eq!(
+                parse(query),
+                Ok(SQLStatement::Transaction(expected)),
+                "{}",
+                query
+            );
+            assert_eq!(format_sql(&format_sql(query).unwrap()), format_sql(query));
+        }
+
+        for query in ["BEGIN WORK", "COMMIT TRANSACTION x", "ROLLBACK before_bulk"] {
+            assert!(parse(query).is_err(), "Expected error for query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_savepoint_statements() {
+        let cases = [
+            (
+                "SAVEPOINT before_bulk",
+                SavepointStatement::Define("before_bulk".to_string()),
+            ),
+            (
+                "ROLLBACK TO before_bulk",
+                SavepointStatement::RollbackTo("before_bulk".to_string()),
+            ),
+            (
+                "ROLLBACK TO SAVEPOINT before_bulk",
+                SavepointStatement::RollbackTo("before_bulk".to_string()),
+            ),
+            (
+                "RELEASE before_bulk",
+                SavepointStatement::Release("before_bulk".to_string()),
+            ),
+            (
+                "RELEASE SAVEPOINT before_bulk",
+                SavepointStatement::Release("before_bulk".to_string()),
+            ),
+        ];
+        for (query, expected) in cases {
+            let result = parse(query);
+            assert_eq!(result, Ok(SQLStatement::Savepoint(expected)), "{}", query);
+            assert_eq!(format_sql(&format_sql(query).unwrap()), format_sql(query));
+        }
+
+        for query in ["SAVEPOINT", "ROLLBACK before_bulk", "RELEASE SAVEPOINT"] {
+            let result = parse(query);
+            assert!(result.is_err(), "Expected error for query: {}", query);
+        }
+    }
+
     #[test]
     fn test_more_invalid_syntax() {
         // More examples of invalid SQL syntax
@@ -621,6 +3270,7 @@ mod tests {
             "DROP TABLE", // Missing table name
             "DROP INDEX", // Missing index name
             // Miscellaneous
+            "SELECT 'oops",                 // Unterminated string
             "ALTER TABLE users ADD COLUMN", // Missing column definition
             "SELECT * FORM users",          // Typo in 'FROM'
             "GRANT SELECT ON TO user",      // Missing object name
@@ -628,8 +3278,7 @@ mod tests {
         ];
 
         for query in invalid_queries {
-            let mut parser = Parser::new(query.to_string());
-            let result = parser.parse();
+            let result = parse(query);
             assert!(result.is_err(), "Expected error for query: {}", query);
             println!("Error for query '{}': {:?}", query, result.err());
         }
@@ -1,54 +1,218 @@
-use std::{any::Any, char};
+use std::{any::Any, char, collections::HashMap};
 
-use super::{sql_token_types::SQLTokenTypes, token::Token};
+use super::{interner::Interner, sql_token_types::SQLTokenTypes, token::Token};
 
 pub struct Scanner {
     source: String,
     start: usize,
     current: usize,
     line: i64,
+    column: i64,
+    start_line: i64,
+    start_column: i64,
     tokens: Vec<Token>,
+    retain_comments: bool,
+    config: ScannerConfig,
+    interner: Interner,
+}
+
+// The keyword table used when scanning words. Embedders targeting another
+// dialect can drop keywords so they scan as plain identifiers, or add spellings.
+pub struct ScannerConfig {
+    keywords: HashMap<String, SQLTokenTypes>,
+}
+
+impl ScannerConfig {
+    // Keywords are matched case-insensitively and stored uppercased.
+    pub fn with_keyword(mut self, keyword: &str, token_type: SQLTokenTypes) -> Self {
+        self.keywords.insert(keyword.to_uppercase(), token_type);
+        self
+    }
+
+    pub fn without_keyword(mut self, keyword: &str) -> Self {
+        self.keywords.remove(&keyword.to_uppercase());
+        self
+    }
+
+    fn keyword(&self, text: &str) -> SQLTokenTypes {
+        match self.keywords.get(text) {
+            Some(token_type) => token_type.clone(),
+            None => SQLTokenTypes::Identifier,
+        }
+    }
+}
+
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        let keywords = [
+            ("SELECT", SQLTokenTypes::Select),
+            ("INSERT", SQLTokenTypes::Insert),
+            ("DELETE", SQLTokenTypes::Delete),
+            ("UPDATE", SQLTokenTypes::Update),
+            ("CREATE", SQLTokenTypes::Create),
+            ("DROP", SQLTokenTypes::Drop),
+            ("FROM", SQLTokenTypes::From),
+            ("WHERE", SQLTokenTypes::Where),
+            ("INTO", SQLTokenTypes::Into),
+            ("VALUES", SQLTokenTypes::Values),
+            ("TRUNCATE", SQLTokenTypes::Truncate),
+            ("RENAME", SQLTokenTypes::Rename),
+            ("ALTER", SQLTokenTypes::Alter),
+            ("VACUUM", SQLTokenTypes::Vacuum),
+            ("SET", SQLTokenTypes::Set),
+            ("BEGIN", SQLTokenTypes::Begin),
+            ("COMMIT", SQLTokenTypes::Commit),
+            ("ROLLBACK", SQLTokenTypes::Rollback),
+            ("SAVEPOINT", SQLTokenTypes::Savepoint),
+            ("TABLE", SQLTokenTypes::Table),
+            ("PRIMARY", SQLTokenTypes::Primary),
+            ("KEY", SQLTokenTypes::Key),
+            ("UNIQUE", SQLTokenTypes::Unique),
+            ("AND", SQLTokenTypes::And),
+            ("NOT", SQLTokenTypes::Not),
+            ("NULL", SQLTokenTypes::Null),
+            ("IS", SQLTokenTypes::IS),
+            ("OR", SQLTokenTypes::OR),
+            ("TRUE", SQLTokenTypes::True),
+            ("FALSE", SQLTokenTypes::False),
+            ("IN", SQLTokenTypes::In),
+            ("LIKE", SQLTokenTypes::Like),
+            ("ESCAPE", SQLTokenTypes::Escape),
+            ("BETWEEN", SQLTokenTypes::Between),
+            ("DISTINCT", SQLTokenTypes::Distinct),
+            ("FOR", SQLTokenTypes::For),
+            ("RETURNING", SQLTokenTypes::Returning),
+            ("LIMIT", SQLTokenTypes::Limit),
+            ("AUTOINCREMENT", SQLTokenTypes::Autoincrement),
+            ("EXPLAIN", SQLTokenTypes::Explain),
+            ("FOREIGN", SQLTokenTypes::Foreign),
+            ("REFERENCES", SQLTokenTypes::References),
+            ("ON", SQLTokenTypes::On),
+            ("CASCADE", SQLTokenTypes::Cascade),
+            ("RESTRICT", SQLTokenTypes::Restrict),
+            ("ORDER", SQLTokenTypes::Order),
+            ("BY", SQLTokenTypes::By),
+            ("ASC", SQLTokenTypes::Asc),
+            ("DESC", SQLTokenTypes::Desc),
+            ("RELEASE", SQLTokenTypes::Release),
+            ("TO", SQLTokenTypes::To),
+        ];
+        Self {
+            keywords: keywords
+                .into_iter()
+                .map(|(keyword, token_type)| (keyword.to_string(), token_type))
+                .collect(),
+        }
+    }
 }
 
 impl Scanner {
     pub fn new(source: String) -> Self {
+        Self::new_with_comments(source, false)
+    }
+
+    // With retain_comments set, comments are emitted as Comment trivia tokens
+    // instead of being discarded, which formatting tools need.
+    pub fn new_with_comments(source: String, retain_comments: bool) -> Self {
+        Self::new_with_config(source, retain_comments, ScannerConfig::default())
+    }
+
+    pub fn new_with_config(source: String, retain_comments: bool, config: ScannerConfig) -> Self {
         Self {
             source,
             current: 0,
             start: 0,
             line: 1,
+            column: 0,
+            start_line: 1,
+            start_column: 1,
             tokens: Vec::new(),
+            retain_comments,
+            config,
+            interner: Interner::default(),
         }
     }
-    pub fn scan_tokens(&mut self) -> Vec<Token> {
+
+    pub fn scan_tokens(&mut self) -> Result<Vec<Token>, String> {
         while !self.is_at_end() {
             self.start = self.current;
-            self.scan_token();
+            self.start_line = self.line;
+            self.start_column = self.column + 1;
+            self.scan_token()?;
         }
         self.tokens.push(Token {
             token_type: SQLTokenTypes::Eof,
-            lexeme: "".to_string(),
+            lexeme: "".into(),
             literal: None,
+            line: self.line,
+            column: self.column + 1,
         });
-        return std::mem::take(&mut self.tokens);
+        return Ok(std::mem::take(&mut self.tokens));
     }
 
-    fn scan_token(&mut self) {
+    fn scan_token(&mut self) -> Result<(), String> {
         let c = self.advance();
         match c {
             '(' => self.add_token(SQLTokenTypes::Leftparen, None),
             ')' => self.add_token(SQLTokenTypes::Rightparen, None),
             '*' => self.add_token(SQLTokenTypes::Star, None),
+            '+' => self.add_token(SQLTokenTypes::Plus, None),
+            '-' if self.peek() == '-' => self.handle_line_comment(),
+            '-' => self.add_token(SQLTokenTypes::Minus, None),
+            '/' if self.peek() == '*' => self.handle_block_comment()?,
+            '/' => self.add_token(SQLTokenTypes::Slash, None),
             ',' => self.add_token(SQLTokenTypes::Comma, None),
+            '.' => self.add_token(SQLTokenTypes::Dot, None),
             ';' => self.add_token(SQLTokenTypes::Semicolon, None),
+            '?' => self.add_token(SQLTokenTypes::Parameter, None),
             '>' => self.handle_greater_relational_operator(),
             '<' => self.handle_lesser_relational_operator(),
             '=' => self.add_token(SQLTokenTypes::Equal, None),
-            '\'' => self.handle_string(),
-            '\n' => self.line += 1,
-            _ if c.is_numeric() => self.handle_numberic(),
-            _ if c.is_alphanumeric() => self.handle_alpha_numeric(),
-            _ => {}
+            '!' => self.handle_bang()?,
+            '|' => self.handle_pipe()?,
+            '\'' => self.handle_string()?,
+            _ if c.is_ascii_digit() => self.handle_numberic()?,
+            _ if c.is_ascii_alphabetic() || c == '_' => self.handle_alpha_numeric(),
+            ' ' | '\t' | '\r' | '\n' => {}
+            _ => {
+                return Err(self.error(
+                    &format!("Unexpected character '{}'", c),
+                    self.start_line,
+                    self.start_column,
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    // -- comment until the end of the line
+    fn handle_line_comment(&mut self) {
+        while self.peek() != '\n' && !self.is_at_end() {
+            self.advance();
+        }
+        let text = self.source[self.start + 2..self.current].to_string();
+        self.add_comment(text);
+    }
+
+    /* comment that may span lines */
+    fn handle_block_comment(&mut self) -> Result<(), String> {
+        self.advance();
+        while !(self.peek() == '*' && self.peek_next() == '/') {
+            if self.is_at_end() {
+                return Err(self.error("Unterminated comment", self.start_line, self.start_column));
+            }
+            self.advance();
+        }
+        self.advance();
+        self.advance();
+        let text = self.source[self.start + 2..self.current - 2].to_string();
+        self.add_comment(text);
+        Ok(())
+    }
+
+    fn add_comment(&mut self, text: String) {
+        if self.retain_comments {
+            self.add_token(SQLTokenTypes::Comment, Some(Box::new(text)));
         }
     }
 
@@ -61,6 +225,26 @@ impl Scanner {
         }
     }
 
+    // `!` is only valid as part of the `!=` spelling of not-equal.
+    fn handle_bang(&mut self) -> Result<(), String> {
+        if self.peek() != '=' {
+            return Err(self.error("Expected '=' after '!'", self.start_line, self.start_column));
+        }
+        self.advance();
+        self.add_token(SQLTokenTypes::NotEqual, None);
+        Ok(())
+    }
+
+    // `|` is only valid as part of the `||` concatenation operator.
+    fn handle_pipe(&mut self) -> Result<(), String> {
+        if self.peek() != '|' {
+            return Err(self.error("Expected '|' after '|'", self.start_line, self.start_column));
+        }
+        self.advance();
+        self.add_token(SQLTokenTypes::Concat, None);
+        Ok(())
+    }
+
     fn handle_lesser_relational_operator(&mut self) {
         if self.peek() == '=' {
             self.advance();
@@ -73,21 +257,51 @@ impl Scanner {
         }
     }
 
-    fn handle_string(&mut self) {
+    // Builds the unescaped string value while scanning, supporting
+    // \' \\ \n and \t escape sequences. Any other backslash is kept verbatim,
+    // so a LIKE pattern such as 'a\%' reaches the parser as written.
+    fn handle_string(&mut self) -> Result<(), String> {
+        let mut string_value = String::new();
         while self.peek() != '\'' {
-            self.advance();
+            if self.is_at_end() {
+                return Err(self.error("Unterminated string", self.start_line, self.start_column));
+            }
+
+            let c = self.advance();
+            if c != '\\' {
+                string_value.push(c);
+                continue;
+            }
+
+            if self.is_at_end() {
+                return Err(self.error("Unterminated string", self.start_line, self.start_column));
+            }
+            match self.advance() {
+                '\'' => string_value.push('\''),
+                '\\' => string_value.push('\\'),
+                'n' => string_value.push('\n'),
+                't' => string_value.push('\t'),
+                other => {
+                    string_value.push('\\');
+                    string_value.push(other);
+                }
+            }
         }
         self.advance();
-        let string_value = self.source[self.start..self.current].to_string();
         self.add_token(SQLTokenTypes::String, Some(Box::new(string_value)));
+        Ok(())
     }
 
-    fn handle_numberic(&mut self) {
+    // Integer literals must fit in an i64 and decimals must be finite, rather than
+    // silently rounding or saturating to infinity.
+    fn handle_numberic(&mut self) -> Result<(), String> {
         while self.peek().is_ascii_digit() {
             self.advance();
         }
 
+        let mut is_decimal = false;
         if self.peek() == '.' && self.peek_next().is_ascii_digit() {
+            is_decimal = true;
             self.advance();
 
             while self.peek().is_ascii_digit() {
@@ -95,8 +309,35 @@ impl Scanner {
             }
         }
 
-        let value: f64 = self.source[self.start..self.current].parse().unwrap();
-        self.add_token(SQLTokenTypes::Number, Some(Box::new(value)));
+        // Integers keep their exact i64 value; decimals are f64. 9223372036854775808
+        // is kept as a u64 since, negated, it is i64::MIN; the parser rejects it otherwise.
+        let text = &self.source[self.start..self.current];
+        let value: Option<Box<dyn Any>> = match is_decimal {
+            true => text
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .map(|value| Box::new(value) as Box<dyn Any>),
+            false => match text.parse::<i64>() {
+                Ok(value) => Some(Box::new(value) as Box<dyn Any>),
+                Err(_) => text
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|value| *value == i64::MIN.unsigned_abs())
+                    .map(|value| Box::new(value) as Box<dyn Any>),
+            },
+        };
+        match value {
+            Some(value) => {
+                self.add_token(SQLTokenTypes::Number, Some(value));
+                Ok(())
+            }
+            None => Err(self.error(
+                "numeric literal out of range",
+                self.start_line,
+                self.start_column,
+            )),
+        }
     }
 
     fn handle_alpha_numeric(&mut self) {
@@ -104,43 +345,24 @@ impl Scanner {
             self.advance();
         }
 
-        let text = &self.source[self.start..self.current].to_uppercase();
-        let token_type = match text.as_str() {
-            "SELECT" => SQLTokenTypes::Select,
-            "INSERT" => SQLTokenTypes::Insert,
-            "DELETE" => SQLTokenTypes::Delete,
-            "UPDATE" => SQLTokenTypes::Update,
-            "CREATE" => SQLTokenTypes::Create,
-            "DROP" => SQLTokenTypes::Drop,
-            "FROM" => SQLTokenTypes::From,
-            "WHERE" => SQLTokenTypes::Where,
-            "INTO" => SQLTokenTypes::Into,
-            "VALUES" => SQLTokenTypes::Values,
-            "TRUNCATE" => SQLTokenTypes::Truncate,
-            "RENAME" => SQLTokenTypes::Rename,
-            "ALTER" => SQLTokenTypes::Alter,
-            "SET" => SQLTokenTypes::Set,
-            "COMMIT" => SQLTokenTypes::Commit,
-            "ROLLBACK" => SQLTokenTypes::Rollback,
-            "SAVEPOINT" => SQLTokenTypes::Savepoint,
-            "TABLE" => SQLTokenTypes::Table,
-            "PRIMARY" => SQLTokenTypes::Primary,
-            "KEY" => SQLTokenTypes::Key,
-            "UNIQUE" => SQLTokenTypes::Unique,
-            "AND" => SQLTokenTypes::And,
-            "NOT" => SQLTokenTypes::Not,
-            "NULL" => SQLTokenTypes::Null,
-            "IS" => SQLTokenTypes::IS,
-            "OR" => SQLTokenTypes::OR,
-            _ => SQLTokenTypes::Identifier,
-        };
+        let text = self.source[self.start..self.current].to_uppercase();
+        let token_type = self.config.keyword(&text);
 
         self.add_token(token_type, None);
     }
 
     fn advance(&mut self) -> char {
+        // `current` is a byte offset, so step over the whole UTF-8 sequence.
         let c = self.source[self.current..].chars().next().unwrap();
-        self.current += 1;
+        self.current += c.len_utf8();
+        // Line and column tracking lives here so that newlines consumed anywhere,
+        // including inside string literals, are counted exactly once.
+        if c == '\n' {
+            self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
+        }
         return c;
     }
 
@@ -149,25 +371,285 @@ impl Scanner {
     }
 
     fn peek_next(&self) -> char {
-        if self.current + 1 >= self.source.len() {
-            '\0'
-        } else {
-            self.source.chars().nth(self.current + 1).unwrap()
-        }
+        self.source[self.current..].chars().nth(1).unwrap_or('\0')
     }
 
     fn peek(&mut self) -> char {
-        if self.is_at_end() {
-            return '\0';
-        }
-        return self.source.chars().nth(self.current).unwrap();
+        self.source[self.current..].chars().next().unwrap_or('\0')
+    }
+
+    // All scanner errors report the position they refer to as `line L, column C`.
+    fn error(&self, message: &str, line: i64, column: i64) -> String {
+        format!("{} at line {}, column {}", message, line, column)
     }
 
     fn add_token(&mut self, sql_token_type: SQLTokenTypes, literal: Option<Box<dyn Any>>) {
         self.tokens.push(Token {
             token_type: sql_token_type,
-            lexeme: self.source[self.start..self.current].to_string(),
+            lexeme: self.interner.intern(&self.source[self.start..self.current]),
             literal,
+            line: self.start_line,
+            column: self.start_column,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unterminated_string_is_an_error() {
+        let result = Scanner::new("SELECT 'oops".to_string()).scan_tokens();
+        assert_eq!(
+            result.unwrap_err(),
+            "Unterminated string at line 1, column 8"
+        );
+
+        let result = Scanner::new("SELECT *\nFROM t WHERE a = 'one\ntwo".to_string()).scan_tokens();
+        assert_eq!(
+            result.unwrap_err(),
+            "Unterminated string at line 2, column 18"
+        );
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let result = Scanner::new("SELECT *\n  FROM t @".to_string()).scan_tokens();
+        assert_eq!(
+            result.unwrap_err(),
+            "Unexpected character '@' at line 2, column 10"
+        );
+
+        let result = Scanner::new("SELECT é FROM t".to_string()).scan_tokens();
+        assert_eq!(
+            result.unwrap_err(),
+            "Unexpected character 'é' at line 1, column 8"
+        );
+    }
+
+    fn string_value(source: &str) -> String {
+        let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap();
+        tokens[0]
+            .literal
+            .as_ref()
+            .and_then(|literal| literal.downcast_ref::<String>())
+            .cloned()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        assert_eq!(string_value(r"'plain'"), "plain");
+        assert_eq!(string_value(r"'it\'s'"), "it's");
+        assert_eq!(string_value(r"'back\\slash'"), "back\\slash");
+        assert_eq!(string_value(r"'line\nbreak'"), "line\nbreak");
+        assert_eq!(string_value(r"'tab\there'"), "tab\there");
+
+        assert_eq!(string_value(r"'kept\q'"), "kept\\q");
+        assert_eq!(string_value(r"'a\%'"), "a\\%");
+        assert!(Scanner::new(r"'dangling\".to_string())
+            .scan_tokens()
+            .is_err());
+    }
+
+    #[test]
+    fn test_non_ascii_text() {
+        assert_eq!(string_value("'café'"), "café");
+        assert_eq!(string_value("'ü\\n€'"), "ü\n€");
+
+        let tokens = Scanner::new("-- héllo\nSELECT 'ü', a FROM t".to_string())
+            .scan_tokens()
+            .unwrap();
+        let a = tokens.iter().find(|token| &*token.lexeme == "a").unwrap();
+        assert_eq!((a.line, a.column), (2, 13));
+
+        let tokens = Scanner::new_with_comments("/* naïve */ SELECT 1".to_string(), true)
+            .scan_tokens()
+            .unwrap();
+        assert_eq!(tokens[0].token_type, SQLTokenTypes::Comment);
+        assert_eq!(tokens[1].column, 13);
+    }
+
+    #[test]
+    fn test_comments() {
+        let source = "SELECT a -- trailing\nFROM t /* block\ncomment */ WHERE a = 1 - 2 / 1";
+
+        let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap();
+        assert!(tokens
+            .iter()
+            .all(|token| token.token_type != SQLTokenTypes::Comment));
+        assert!(tokens
+            .iter()
+            .any(|token| token.token_type == SQLTokenTypes::Minus));
+        assert!(tokens
+            .iter()
+            .any(|token| token.token_type == SQLTokenTypes::Slash));
+
+        let tokens = Scanner::new_with_comments(source.to_string(), true)
+            .scan_tokens()
+            .unwrap();
+        let comments: Vec<(&str, i64, i64)> = tokens
+            .iter()
+            .filter(|token| token.token_type == SQLTokenTypes::Comment)
+            .map(|token| (&*token.lexeme, token.line, token.column))
+            .collect();
+        assert_eq!(
+            comments,
+            vec![("-- trailing", 1, 10), ("/* block\ncomment */", 2, 8)]
+        );
+        let text = tokens
+            .iter()
+            .find(|token| token.token_type == SQLTokenTypes::Comment)
+            .and_then(|token| token.literal.as_ref())
+            .and_then(|literal| literal.downcast_ref::<String>())
+            .unwrap();
+        assert_eq!(text, " trailing");
+
+        let result = Scanner::new("SELECT a /* never closed".to_string()).scan_tokens();
+        assert_eq!(
+            result.unwrap_err(),
+            "Unterminated comment at line 1, column 10"
+        );
+    }
+
+    #[test]
+    fn test_not_equal_spellings() {
+        for source in ["a <> 1", "a != 1"] {
+            let tokens = Scanner::new(source.to_string()).scan_tokens().unwrap();
+            assert_eq!(tokens[1].token_type, SQLTokenTypes::NotEqual, "{}", source);
+        }
+
+        let result = Scanner::new("a ! 1".to_string()).scan_tokens();
+        assert_eq!(
+            result.unwrap_err(),
+            "Expected '=' after '!' at line 1, column 3"
+        );
+    }
+
+    #[test]
+    fn test_identifiers_may_start_with_underscore() {
+        let tokens = Scanner::new("SELECT _x, __y1 FROM _t".to_string())
+            .scan_tokens()
+            .unwrap();
+        let identifiers: Vec<(&str, i64)> = tokens
+            .iter()
+            .filter(|token| token.token_type == SQLTokenTypes::Identifier)
+            .map(|token| (&*token.lexeme, token.column))
+            .collect();
+        assert_eq!(identifiers, vec![("_x", 8), ("__y1", 12), ("_t", 22)]);
+    }
+
+    #[test]
+    fn test_tracks_lines_and_columns() {
+        let tokens = Scanner::new("SELECT name,\n  'multi\nline'\nFROM users".to_string())
+            .scan_tokens()
+            .unwrap();
+        let from = tokens
+            .iter()
+            .find(|token| token.token_type == SQLTokenTypes::From)
+            .unwrap();
+        assert_eq!((from.line, from.column), (4, 1));
+
+        let tokens = Scanner::new("SELECT *\nFROM users\nWHERE id = 1".to_string())
+            .scan_tokens()
+            .unwrap();
+        let id = tokens.iter().find(|token| &*token.lexeme == "id").unwrap();
+        assert_eq!((id.line, id.column), (3, 7));
+    }
+
+    #[test]
+    fn test_scanner_config_keywords() {
+        let token_types = |sql: &str, config: ScannerConfig| {
+            Scanner::new_with_config(sql.to_string(), false, config)
+                .scan_tokens()
+                .unwrap()
+                .into_iter()
+                .map(|token| token.token_type)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            token_types("select key", ScannerConfig::default())[1],
+            SQLTokenTypes::Key
+        );
+        assert_eq!(
+            token_types(
+                "select key",
+                ScannerConfig::default().without_keyword("KEY")
+            )[1],
+            SQLTokenTypes::Identifier
+        );
+        assert_eq!(
+            token_types("fetch", ScannerConfig::default())[0],
+            SQLTokenTypes::Identifier
+        );
+        assert_eq!(
+            token_types(
+                "fetch",
+                ScannerConfig::default().with_keyword("Fetch", SQLTokenTypes::Select)
+            )[0],
+            SQLTokenTypes::Select
+        );
+    }
+
+    #[test]
+    fn test_numeric_literal_range() {
+        let tokens = Scanner::new(format!("SELECT {}", i64::MAX))
+            .scan_tokens()
+            .unwrap();
+        assert_eq!(tokens[1].token_type, SQLTokenTypes::Number);
+        assert_eq!(
+            tokens[1]
+                .literal
+                .as_ref()
+                .and_then(|literal| literal.downcast_ref::<i64>()),
+            Some(&i64::MAX)
+        );
+
+        let tokens = Scanner::new("SELECT -9223372036854775808".to_string())
+            .scan_tokens()
+            .unwrap();
+        assert_eq!(
+            tokens[2]
+                .literal
+                .as_ref()
+                .and_then(|literal| literal.downcast_ref::<u64>()),
+            Some(&i64::MIN.unsigned_abs())
+        );
+        assert!(Scanner::new("SELECT 9223372036854775809".to_string())
+            .scan_tokens()
+            .is_err());
+
+        let result = Scanner::new("SELECT *\nFROM t WHERE id = 99999999999999999999".to_string())
+            .scan_tokens();
+        assert_eq!(
+            result.unwrap_err(),
+            "numeric literal out of range at line 2, column 19"
+        );
+
+        let huge_decimal = format!("SELECT {}.5", "9".repeat(400));
+        assert!(Scanner::new(huge_decimal).scan_tokens().is_err());
+        assert!(Scanner::new("SELECT 1.5, 0.25".to_string())
+            .scan_tokens()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_repeated_lexemes_are_interned_once() {
+        let columns = vec!["price"; 1000].join(", ");
+        let mut scanner = Scanner::new(format!("SELECT {} FROM products", columns));
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let prices: Vec<_> = tokens
+            .iter()
+            .filter(|token| &*token.lexeme == "price")
+            .collect();
+        assert_eq!(prices.len(), 1000);
+        assert!(prices
+            .iter()
+            .all(|token| std::rc::Rc::ptr_eq(&token.lexeme, &prices[0].lexeme)));
+        // SELECT, price, `,`, FROM and products.
+        assert_eq!(scanner.interner.len(), 5);
+    }
+}
@@ -11,7 +11,9 @@ pub enum SQLTokenTypes {
     Truncate,
     Rename,
     Alter,
+    Vacuum,
     //TCL
+    Begin,
     Commit,
     Rollback,
     Savepoint,
@@ -24,7 +26,11 @@ pub enum SQLTokenTypes {
     Leftparen,
     Rightparen,
     Star,
+    Plus,
+    Minus,
+    Slash,
     Comma,
+    Dot,
     Semicolon,
     Newline,
 
@@ -55,6 +61,33 @@ pub enum SQLTokenTypes {
     IS,
     GreaterThanOrEqualTo,
     LesserThanOrEqualTo,
+
+    True,
+    False,
+    In,
+    Explain,
+    Foreign,
+    References,
+    On,
+    Cascade,
+    Restrict,
+    Order,
+    By,
+    Asc,
+    Desc,
+    Parameter,
+    Comment,
+    Release,
+    To,
+    Concat,
+    Like,
+    Escape,
+    Between,
+    Distinct,
+    For,
+    Returning,
+    Limit,
+    Autoincrement,
 }
 
 impl Clone for SQLTokenTypes {
@@ -69,6 +102,8 @@ impl Clone for SQLTokenTypes {
             Self::Truncate => Self::Truncate,
             Self::Rename => Self::Rename,
             Self::Alter => Self::Alter,
+            Self::Vacuum => Self::Vacuum,
+            Self::Begin => Self::Begin,
             Self::Commit => Self::Commit,
             Self::Rollback => Self::Rollback,
             Self::Savepoint => Self::Savepoint,
@@ -79,7 +114,11 @@ impl Clone for SQLTokenTypes {
             Self::Leftparen => Self::Leftparen,
             Self::Rightparen => Self::Rightparen,
             Self::Star => Self::Star,
+            Self::Plus => Self::Plus,
+            Self::Minus => Self::Minus,
+            Self::Slash => Self::Slash,
             Self::Comma => Self::Comma,
+            Self::Dot => Self::Dot,
             Self::Semicolon => Self::Semicolon,
             Self::Newline => Self::Newline,
             Self::Greater => Self::Greater,
@@ -103,6 +142,32 @@ impl Clone for SQLTokenTypes {
             Self::IS => Self::IS,
             Self::GreaterThanOrEqualTo => Self::GreaterThanOrEqualTo,
             Self::LesserThanOrEqualTo => Self::LesserThanOrEqualTo,
+            Self::True => Self::True,
+            Self::False => Self::False,
+            Self::In => Self::In,
+            Self::Explain => Self::Explain,
+            Self::Foreign => Self::Foreign,
+            Self::References => Self::References,
+            Self::On => Self::On,
+            Self::Cascade => Self::Cascade,
+            Self::Restrict => Self::Restrict,
+            Self::Order => Self::Order,
+            Self::By => Self::By,
+            Self::Asc => Self::Asc,
+            Self::Desc => Self::Desc,
+            Self::Parameter => Self::Parameter,
+            Self::Comment => Self::Comment,
+            Self::Release => Self::Release,
+            Self::To => Self::To,
+            Self::Concat => Self::Concat,
+            Self::Like => Self::Like,
+            Self::Escape => Self::Escape,
+            Self::Between => Self::Between,
+            Self::Distinct => Self::Distinct,
+            Self::For => Self::For,
+            Self::Returning => Self::Returning,
+            Self::Limit => Self::Limit,
+            Self::Autoincrement => Self::Autoincrement,
         }
     }
 }
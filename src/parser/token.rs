@@ -1,9 +1,23 @@
 use super::sql_token_types::SQLTokenTypes;
-use std::any::Any;
+use std::{any::Any, fmt, rc::Rc};
 
 #[derive(Debug)]
 pub struct Token {
     pub token_type: SQLTokenTypes,
-    pub lexeme: String,
+    // Shared with every other token spelled the same way in the scanned source.
+    pub lexeme: Rc<str>,
     pub literal: Option<Box<dyn Any>>,
+    pub line: i64,
+    pub column: i64,
+}
+
+// Renders a token as TYPE(lexeme)@line:column
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}({})@{}:{}",
+            self.token_type, self.lexeme, self.line, self.column
+        )
+    }
 }
@@ -0,0 +1,52 @@
+use monkdb::parser::{ast::SQLStatement, tokenize, Parser};
+
+#[test]
+fn test_public_api_parses_each_statement_type() {
+    let queries = vec![
+        "SELECT id, name FROM users WHERE id = 1",
+        "INSERT INTO users (id, name) VALUES (1, 'John')",
+        "UPDATE users SET name = 'Jane' WHERE id = 1",
+        "DELETE FROM users WHERE id = 1",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)",
+        "DROP TABLE users",
+        "EXPLAIN SELECT * FROM users WHERE id = 1",
+        "SAVEPOINT before_cleanup",
+        "BEGIN TRANSACTION",
+        "ALTER TABLE users RENAME TO customers",
+        "COMMENT ON COLUMN users.name IS 'Display name'",
+        "VACUUM users",
+    ];
+
+    for query in queries {
+        let result = Parser::new(query.to_string()).and_then(|mut parser| parser.parse());
+        let matched = match &result {
+            Ok(SQLStatement::Select(_)) => query.starts_with("SELECT"),
+            Ok(SQLStatement::Insert(_)) => query.starts_with("INSERT"),
+            Ok(SQLStatement::Update(_)) => query.starts_with("UPDATE"),
+            Ok(SQLStatement::Delete(_)) => query.starts_with("DELETE"),
+            Ok(SQLStatement::Create(_)) => query.starts_with("CREATE"),
+            Ok(SQLStatement::Drop(_)) => query.starts_with("DROP"),
+            Ok(SQLStatement::Explain(_)) => query.starts_with("EXPLAIN"),
+            Ok(SQLStatement::Savepoint(_)) => query.starts_with("SAVEPOINT"),
+            Ok(SQLStatement::Transaction(_)) => query.starts_with("BEGIN"),
+            Ok(SQLStatement::Alter(_)) => query.starts_with("ALTER"),
+            Ok(SQLStatement::Comment(_)) => query.starts_with("COMMENT"),
+            Ok(SQLStatement::Vacuum(_)) => query.starts_with("VACUUM"),
+            Err(_) => false,
+        };
+        assert!(matched, "Unexpected result for '{}': {:?}", query, result);
+    }
+}
+
+#[test]
+fn test_scanner_output_feeds_the_parser() {
+    let sql = "INSERT INTO users (id, name) VALUES (1, 'John'), (2, 'Jane')";
+    let tokens = tokenize(sql).expect("query should scan");
+    let statement = Parser::from_tokens(tokens).parse();
+
+    assert_eq!(
+        statement,
+        Parser::new(sql.to_string()).and_then(|mut parser| parser.parse())
+    );
+    assert!(matches!(statement, Ok(SQLStatement::Insert(insert)) if insert.values.len() == 2));
+}